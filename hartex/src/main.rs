@@ -1,15 +1,76 @@
 //! # `hartex` - The HarTex Binary Crate
 //!
-//! This crate contains the main function which calls the `hartex_main` "main function" in the
-//! `hartex_driver` crate which glues everything together.
+//! This crate contains the main function which parses the command line and calls the "main
+//! function" of the requested subcommand in the `hartex_driver` crate which glues everything
+//! together.
+//!
+//! The subcommands are:
+//!
+//! - `run` (the default): runs the bot;
+//! - `migrate`: applies the pending database migrations;
+//! - `sync-commands [--dry-run]`: registers the global commands that have not been registered;
+//! - `validate-config <file>`: validates a guild configuration file;
+//! - `whitelist list|add|remove`: manages the guild whitelist;
+//! - `replay [--live] <events.jsonl>`: replays recorded gateway events through the event handlers;
+//!   without `--live`, no request is sent to Discord and nothing is written to the database.
+//!
+//! Only `run` connects to the gateway. The global flags `--config`, `--log-level`,
+//! `--log-format`, `--database-backend`, `--shards` and `--process-name` override the bot
+//! configuration loaded from `hartex.toml` and the environment variables.
+
+#![feature(format_args_capture)]
 
-use std::time::Duration;
+use std::{
+    path::{
+        Path,
+        PathBuf
+    },
+    time::Duration
+};
+
+use clap::{
+    App,
+    AppSettings,
+    Arg,
+    ArgMatches,
+    SubCommand
+};
 
 use tokio::runtime::Builder;
 
-use hartex_core::error::HarTexResult;
+use hartex_core::{
+    discord::model::id::GuildId,
+    error::{
+        HarTexError,
+        HarTexResult
+    }
+};
+
+use hartex_driver::{
+    config::ConfigOverrides,
+    maintenance::{
+        self,
+        WhitelistAction
+    }
+};
 
 pub fn main() -> HarTexResult<()> {
+    let matches = cli().get_matches();
+    let overrides = ConfigOverrides {
+        config_path: matches.value_of("config").map(PathBuf::from),
+        log_level: matches.value_of("log-level").map(String::from),
+        log_format: matches.value_of("log-format").map(String::from),
+        database_backend: matches.value_of("database-backend").map(String::from),
+        shards: matches.value_of("shards").map(String::from),
+        process_name: matches.value_of("process-name").map(String::from)
+    };
+
+    // validating a configuration file requires neither the bot configuration nor the runtime
+    if let ("validate-config", Some(args)) = matches.subcommand() {
+        // unwrapping here is fine as the argument is required
+        return maintenance::hartex_validate_config(Path::new(args.value_of("FILE").unwrap()));
+    }
+
     let tokio_runtime = Builder::new_multi_thread()
         .enable_io()
         .enable_time()
@@ -18,9 +79,163 @@ pub fn main() -> HarTexResult<()> {
         .unwrap();
 
     tokio_runtime.block_on(async move {
-        hartex_driver::hartex_main().await
+        match matches.subcommand() {
+            ("migrate", _) => hartex_driver::hartex_migrate(overrides).await,
+            ("sync-commands", Some(args)) => maintenance::hartex_sync_commands(overrides, args.is_present("dry-run")).await,
+            ("whitelist", Some(args)) => maintenance::hartex_whitelist(overrides, whitelist_action(args)?).await,
+            ("replay", Some(args)) => {
+                // unwrapping here is fine as the argument is required
+                maintenance::hartex_replay(overrides, Path::new(args.value_of("EVENTS").unwrap()), args.is_present("live")).await
+            }
+            _ => hartex_driver::hartex_main(overrides).await
+        }
     })?;
     tokio_runtime.shutdown_timeout(Duration::from_millis(100));
 
     Ok(())
 }
+
+/// # Function `cli`
+///
+/// Returns the definition of the command line interface.
+fn cli() -> App<'static, 'static> {
+    let guild_id = Arg::with_name("GUILD_ID")
+        .help("The id of the guild")
+        .required(true);
+
+    App::new("hartex")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("HarTex Discord bot")
+        .setting(AppSettings::VersionlessSubcommands)
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("PATH")
+                .help("The path of the bot configuration file [default: hartex.toml]")
+                .takes_value(true)
+                .global(true)
+        )
+        .arg(
+            Arg::with_name("log-level")
+                .long("log-level")
+                .value_name("FILTER")
+                .help("The most verbose log level to log, optionally followed by per-module levels (e.g. `info,hartex_dbmani=verbose`)")
+                .takes_value(true)
+                .global(true)
+        )
+        .arg(
+            Arg::with_name("log-format")
+                .long("log-format")
+                .value_name("FORMAT")
+                .help("The format the messages are logged in")
+                .possible_values(&["text", "json"])
+                .takes_value(true)
+                .global(true)
+        )
+        .arg(
+            Arg::with_name("database-backend")
+                .long("database-backend")
+                .value_name("BACKEND")
+                .help("The database backend to use")
+                .possible_values(&["postgres", "inmemory"])
+                .takes_value(true)
+                .global(true)
+        )
+        .arg(
+            Arg::with_name("shards")
+                .long("shards")
+                .value_name("FROM-TO/TOTAL")
+                .help("The range of shards to run, out of the total number of shards")
+                .takes_value(true)
+                .global(true)
+        )
+        .arg(
+            Arg::with_name("process-name")
+                .long("process-name")
+                .value_name("NAME")
+                .help("The name of the process in the logs and the presence")
+                .takes_value(true)
+                .global(true)
+        )
+        .subcommand(SubCommand::with_name("run").about("Runs the bot (default)"))
+        .subcommand(SubCommand::with_name("migrate").about("Applies the pending database migrations"))
+        .subcommand(
+            SubCommand::with_name("sync-commands")
+                .about("Registers the global commands that have not been registered")
+                .arg(
+                    Arg::with_name("dry-run")
+                        .long("dry-run")
+                        .help("Only lists the commands that would be registered")
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("validate-config")
+                .about("Validates a guild configuration file")
+                .arg(Arg::with_name("FILE").help("The configuration file to validate").required(true))
+        )
+        .subcommand(
+            SubCommand::with_name("whitelist")
+                .about("Manages the guild whitelist")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(SubCommand::with_name("list").about("Lists the whitelisted guilds"))
+                .subcommand(
+                    SubCommand::with_name("add")
+                        .about("Whitelists a guild, or reactivates its whitelist")
+                        .arg(guild_id.clone())
+                        .arg(Arg::with_name("NAME").help("The name of the guild").required(true))
+                )
+                .subcommand(
+                    SubCommand::with_name("remove")
+                        .about("Removes the whitelist of a guild")
+                        .arg(guild_id)
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("replay")
+                .about("Replays recorded gateway events through the event handlers")
+                .arg(Arg::with_name("EVENTS").help("The file containing a raw gateway payload per line").required(true))
+                .arg(
+                    Arg::with_name("live")
+                        .long("live")
+                        .help("Sends the requests of the event handlers to Discord and writes to the database; by default they are only logged")
+                )
+        )
+}
+
+/// # Function `whitelist_action`
+///
+/// Returns the whitelist operation specified with the `whitelist` subcommand.
+///
+/// ## Parameters
+/// - `args`, type `&ArgMatches`: the arguments of the `whitelist` subcommand
+fn whitelist_action(args: &ArgMatches) -> HarTexResult<WhitelistAction> {
+    Ok(match args.subcommand() {
+        ("add", Some(args)) => WhitelistAction::Add {
+            guild_id: parse_guild_id(args)?,
+            // unwrapping here is fine as the argument is required
+            name: args.value_of("NAME").unwrap().to_string()
+        },
+        ("remove", Some(args)) => WhitelistAction::Remove {
+            guild_id: parse_guild_id(args)?
+        },
+        _ => WhitelistAction::List
+    })
+}
+
+/// # Function `parse_guild_id`
+///
+/// Parses the `GUILD_ID` argument of a subcommand.
+///
+/// ## Parameters
+/// - `args`, type `&ArgMatches`: the arguments of the subcommand
+fn parse_guild_id(args: &ArgMatches) -> HarTexResult<GuildId> {
+    // unwrapping here is fine as the argument is required
+    let value = args.value_of("GUILD_ID").unwrap();
+
+    match value.parse::<u64>() {
+        Ok(id) if id != 0 => Ok(GuildId(id)),
+        _ => Err(HarTexError::Custom {
+            message: format!("invalid guild id `{value}`")
+        })
+    }
+}
@@ -2,17 +2,12 @@
 //!
 //! This module implements a check for whether the message author is the global administrator himself.
 
-use std::env;
-
-use hartex_core::{
-    discord::model::id::UserId,
-    error::{
-        HarTexError,
-        HarTexResult
-    }
+use hartex_core::error::{
+    HarTexError,
+    HarTexResult
 };
 
-use hartex_logging::Logger;
+use hartex_logging::error;
 
 use hartex_utils::FutureRetType;
 
@@ -32,8 +27,8 @@ pub struct IsGlobAdmin;
 impl Check for IsGlobAdmin {
     type CheckRetType = ();
 
-    fn execute<'asynchronous_trait>(_: CommandContext, params: CheckParams) -> FutureRetType<'asynchronous_trait, Self::CheckRetType> {
-        Box::pin(execute_isglobadmin_check(params))
+    fn execute<'asynchronous_trait>(ctx: CommandContext, params: CheckParams) -> FutureRetType<'asynchronous_trait, Self::CheckRetType> {
+        Box::pin(execute_isglobadmin_check(ctx, params))
     }
 }
 
@@ -42,27 +37,16 @@ impl Check for IsGlobAdmin {
 /// Executes the `isglobadmin` check.
 ///
 /// ## Parameters
+/// - `ctx`, type `CommandContext`: the command context to obtain the global administrator from
 /// - `params`, type `CheckParams`: the check parameters
-async fn execute_isglobadmin_check(params: CheckParams) -> HarTexResult<<IsGlobAdmin as Check>::CheckRetType> {
-    let user_id = match env::var("GLOBAL_ADMINISTRATOR_UID") {
-        Ok(token) => UserId(token.parse().unwrap_or(0u64)),
-        Err(var_error) => {
-            Logger::error(
-                format!(
-                    "could not obtain the global administrator user id environment variable: {}",
-                    var_error
-                ),
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+async fn execute_isglobadmin_check(ctx: CommandContext, params: CheckParams) -> HarTexResult<<IsGlobAdmin as Check>::CheckRetType> {
+    let user_id = match ctx.global_administrator_id {
+        Some(user_id) => user_id,
+        None => {
+            error!("no global administrator is configured");
 
             return Err(HarTexError::Custom {
-                message: format!(
-                    "could not obtain the global administrator user id environment variable: {}",
-                    var_error
-                )
+                message: String::from("no global administrator is configured")
             });
         }
     };
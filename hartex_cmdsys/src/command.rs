@@ -2,8 +2,17 @@
 //!
 //! This module defines a trait for commands to implement.
 
+use hartex_conftoml::nightly::NightlyFeature;
+
 use hartex_core::discord::{
-    cache_inmemory::InMemoryCache,
+    cache_inmemory::{
+        InMemoryCache,
+        ResourceType
+    },
+    gateway::{
+        EventTypeFlags,
+        Intents
+    },
     model::application::command::CommandOption
 };
 
@@ -22,6 +31,10 @@ use crate::context::CommandContext;
 /// - `required_cmdopts`; return type `Vec<CommandOption>`: a vector of required command options
 /// - `optional_cmdopts`; return type `Vec<CommandOption>`: a vector of optional command options
 /// - `enabled_by_default`; return type `bool`: whether the slash command is enabled by default when added to a guild
+/// - `required_nightly_feature`; return type `Option<NightlyFeature>`: the nightly feature the guild must opt in to for the command to be usable
+/// - `required_intents`; return type `Intents`: the gateway intents the command needs, in addition to those the bot always uses
+/// - `required_event_types`; return type `EventTypeFlags`: the gateway events the command needs to be received, in addition to those the bot always handles
+/// - `required_resource_types`; return type `ResourceType`: the resources the command needs to be cached
 pub trait Command {
     fn name(&self) -> String;
 
@@ -42,6 +55,22 @@ pub trait Command {
     fn enabled_by_default(&self) -> bool {
         true
     }
+
+    fn required_nightly_feature(&self) -> Option<NightlyFeature> {
+        None
+    }
+
+    fn required_intents(&self) -> Intents {
+        Intents::empty()
+    }
+
+    fn required_event_types(&self) -> EventTypeFlags {
+        EventTypeFlags::empty()
+    }
+
+    fn required_resource_types(&self) -> ResourceType {
+        ResourceType::empty()
+    }
 }
 
 /// # Enumeration `CommandType`
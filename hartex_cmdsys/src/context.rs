@@ -10,9 +10,20 @@ use std::{
 use hartex_core::discord::{
     gateway::Cluster,
     http::Client,
-    model::application::interaction::Interaction
+    model::{
+        application::interaction::Interaction,
+        id::UserId
+    },
+    standby::Standby
 };
 
+use hartex_dbmani::{
+    confcache::GuildConfigCache,
+    repository::SharedRepository
+};
+
+use hartex_eventsys::emitter::EventEmitter;
+
 /// # Struct `CommandContext`
 ///
 /// The command context used for command invocation.
@@ -28,7 +39,12 @@ pub struct CommandContext {
 pub struct CommandContextInner {
     pub http: Client,
     pub cluster: Cluster,
-    pub interaction: Interaction
+    pub interaction: Interaction,
+    pub repository: SharedRepository,
+    pub config_cache: GuildConfigCache,
+    pub emitter: EventEmitter,
+    pub standby: Arc<Standby>,
+    pub global_administrator_id: Option<UserId>
 }
 
 impl Deref for CommandContext {
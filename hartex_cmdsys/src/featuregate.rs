@@ -0,0 +1,153 @@
+//! # The `featuregate` Module
+//!
+//! This module implements the gating of commands and event handlers behind the nightly features
+//! that guilds opt in to in their configuration.
+
+use hartex_conftoml::nightly::NightlyFeature;
+
+use hartex_core::discord::model::id::GuildId;
+
+use hartex_dbmani::confcache::GuildConfigCache;
+
+use hartex_logging::warn;
+
+/// # Asynchronous Function `is_enabled`
+///
+/// Returns whether something requiring a nightly feature may be used in a guild. Anything that
+/// does not require a nightly feature may always be used; otherwise, the guild must have opted in
+/// to the feature. Outside of guilds, and in guilds whose configuration cannot be retrieved,
+/// nightly features are disabled.
+///
+/// ## Parameters
+/// - `config_cache`, type `&GuildConfigCache`: the configuration cache to retrieve the guild
+///                                             configuration from
+/// - `guild_id`, type `Option<GuildId>`: the guild, if any
+/// - `feature`, type `Option<NightlyFeature>`: the required nightly feature, if any
+pub async fn is_enabled(config_cache: &GuildConfigCache, guild_id: Option<GuildId>, feature: Option<NightlyFeature>) -> bool {
+    let (feature, guild_id) = match (feature, guild_id) {
+        (None, _) => return true,
+        (Some(_), None) => return false,
+        (Some(feature), Some(guild_id)) => (feature, guild_id)
+    };
+
+    match config_cache.get(guild_id).await {
+        Ok(config) => config.NightlyFeatures.is_enabled(feature),
+        Err(error) => {
+            warn!("could not retrieve configuration of guild {guild_id} to check nightly feature `{feature}`; treating it as disabled: {error:?}");
+
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use hartex_conftoml::nightly::NightlyFeature;
+
+    use hartex_core::discord::{
+        cache_inmemory::InMemoryCache,
+        model::id::GuildId
+    };
+
+    use hartex_dbmani::{
+        confcache::{
+            self,
+            GuildConfigCache
+        },
+        repository::inmemory::InMemoryRepository
+    };
+
+    use hartex_utils::FutureRetType;
+
+    use crate::{
+        command::{
+            Command,
+            CommandType
+        },
+        context::CommandContext
+    };
+
+    const OPTED_IN: GuildId = GuildId(1);
+    const NOT_OPTED_IN: GuildId = GuildId(2);
+    const UNKNOWN: GuildId = GuildId(3);
+
+    /// # Struct `ThreadsCommand`
+    ///
+    /// A command requiring the `threads` nightly feature.
+    struct ThreadsCommand;
+
+    impl Command for ThreadsCommand {
+        fn name(&self) -> String {
+            String::from("threads")
+        }
+
+        fn description(&self) -> String {
+            String::from("A command requiring the threads nightly feature.")
+        }
+
+        fn command_type(&self) -> CommandType {
+            CommandType::ChatInput
+        }
+
+        fn execute<'asynchronous_trait>(&self, _: CommandContext, _: InMemoryCache) -> FutureRetType<'asynchronous_trait, ()> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn required_nightly_feature(&self) -> Option<NightlyFeature> {
+            Some(NightlyFeature::Threads)
+        }
+    }
+
+    /// # Function `config`
+    ///
+    /// Returns a guild configuration that does or does not opt in to the `threads` nightly
+    /// feature.
+    fn config(threads: bool) -> String {
+        format!("version = 2\nDashboardAccess = []\n\n[GuildConfiguration]\n\n[NightlyFeatures]\nthreads = {threads}\n")
+    }
+
+    /// # Function `config_cache`
+    ///
+    /// Returns a configuration cache with a guild that has opted in to the `threads` nightly
+    /// feature and one that has not.
+    fn config_cache() -> GuildConfigCache {
+        let repository = InMemoryRepository::new();
+        repository.insert_guild_config(OPTED_IN, config(true));
+        repository.insert_guild_config(NOT_OPTED_IN, config(false));
+
+        GuildConfigCache::new(Arc::new(repository), confcache::DEFAULT_TTL)
+    }
+
+    #[tokio::test]
+    async fn gated_command_is_refused_in_a_guild_that_has_not_opted_in() {
+        let feature = ThreadsCommand.required_nightly_feature();
+
+        assert!(!super::is_enabled(&config_cache(), Some(NOT_OPTED_IN), feature).await);
+    }
+
+    #[tokio::test]
+    async fn gated_command_is_allowed_in_a_guild_that_has_opted_in() {
+        let feature = ThreadsCommand.required_nightly_feature();
+
+        assert!(super::is_enabled(&config_cache(), Some(OPTED_IN), feature).await);
+    }
+
+    #[tokio::test]
+    async fn gated_command_is_refused_outside_of_known_guilds() {
+        let config_cache = config_cache();
+        let feature = ThreadsCommand.required_nightly_feature();
+
+        assert!(!super::is_enabled(&config_cache, None, feature).await);
+        assert!(!super::is_enabled(&config_cache, Some(UNKNOWN), feature).await);
+    }
+
+    #[tokio::test]
+    async fn ungated_command_is_always_allowed() {
+        let config_cache = config_cache();
+
+        assert!(super::is_enabled(&config_cache, Some(NOT_OPTED_IN), None).await);
+        assert!(super::is_enabled(&config_cache, None, None).await);
+    }
+}
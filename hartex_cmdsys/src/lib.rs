@@ -3,7 +3,10 @@
 //! The `hartex_cmdsys` library contains an implementation of a command system for HarTex Discord
 //! bot, including a command parser, as well as various utilities for the implementation.
 
+#![feature(format_args_capture)]
+
 pub mod checks;
 pub mod command;
 pub mod context;
+pub mod featuregate;
 pub mod framework;
@@ -0,0 +1,27 @@
+//! # `hartex_schema` - JSON Schema Generator for the HarTex Guild Configuration
+//!
+//! Writes the JSON Schema of the guild TOML configuration to the path given as the first argument,
+//! or to standard output if no path is given.
+
+#![feature(format_args_capture)]
+
+use std::{
+    env,
+    fs,
+    process
+};
+
+fn main() {
+    let schema = hartex_conftoml::schema::json_schema();
+
+    match env::args().nth(1) {
+        Some(path) => {
+            if let Err(error) = fs::write(&path, schema) {
+                eprintln!("failed to write schema to `{path}`: {error}");
+
+                process::exit(1);
+            }
+        }
+        None => println!("{schema}")
+    }
+}
@@ -2,13 +2,92 @@
 //!
 //! This module contains configuration models specifically for dashboard access configuration.
 
-use serde::Deserialize;
+use schemars::JsonSchema;
+
+use serde::{
+    de::Error,
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer
+};
+
+use hartex_core::discord::model::id::UserId;
 
 /// # Struct `DashboardAccess`
 ///
 /// Represents the dashboard access of a user.
-#[derive(Deserialize)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[schemars(description = "The dashboard access of a user.")]
 pub struct DashboardAccess {
-    pub userId: u64,
-    pub accessLevel: u8
+    /// The id of the user.
+    #[serde(deserialize_with = "deserialize_user_id", serialize_with = "serialize_user_id")]
+    #[schemars(with = "u64")]
+    pub userId: UserId,
+    /// The access level of the user.
+    pub accessLevel: AccessLevel
+}
+
+/// # Enum `AccessLevel`
+///
+/// The access level of a user to the configuration of a guild; each level includes the
+/// permissions of the levels below it.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, JsonSchema, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[schemars(description = "The access level of a user: `viewer`, `editor`, `admin` or `owner`; each level includes the permissions of the levels below it.")]
+pub enum AccessLevel {
+    /// # Enum Variant `AccessLevel::Viewer`
+    ///
+    /// May view the configuration.
+    Viewer,
+
+    /// # Enum Variant `AccessLevel::Editor`
+    ///
+    /// May edit the configuration, except for the dashboard access.
+    Editor,
+
+    /// # Enum Variant `AccessLevel::Admin`
+    ///
+    /// May edit the configuration, including the dashboard access of users who are not owners.
+    Admin,
+
+    /// # Enum Variant `AccessLevel::Owner`
+    ///
+    /// May edit the whole configuration, including the dashboard access of other owners.
+    Owner
+}
+
+impl AccessLevel {
+    /// # Constant `AccessLevel::NAMES`
+    ///
+    /// The names of the access levels, as they are written in the configuration.
+    pub const NAMES: &'static [&'static str] = &["viewer", "editor", "admin", "owner"];
+
+    /// # Instance Method `AccessLevel::name`
+    ///
+    /// Returns the name of the access level, as it is written in the configuration.
+    pub fn name(self) -> &'static str {
+        Self::NAMES[self as usize]
+    }
+}
+
+/// # Function `deserialize_user_id`
+///
+/// Deserializes a user id from a positive integer.
+fn deserialize_user_id<'deserialize, D>(deserializer: D) -> Result<UserId, D::Error>
+where
+    D: Deserializer<'deserialize> {
+    match u64::deserialize(deserializer)? {
+        0 => Err(D::Error::custom("a user id must be a positive integer")),
+        id => Ok(UserId(id))
+    }
+}
+
+/// # Function `serialize_user_id`
+///
+/// Serializes a user id as an integer, rather than as the string that Discord uses.
+fn serialize_user_id<S>(user_id: &UserId, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer {
+    serializer.serialize_u64(user_id.0)
 }
@@ -2,26 +2,45 @@
 //!
 //! This module contains configuration models specifically for guild-specific configuration.
 
+use schemars::JsonSchema;
+
 use serde::{
     de,
-    Deserialize
+    Deserialize,
+    Serialize
 };
 
-mod tz;
+pub mod tz;
 
 /// # Struct `GuildConfiguration`
 ///
 /// Represents guild-specific configuration.
-#[derive(Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[schemars(description = "General configuration of the bot in the guild.")]
 pub struct GuildConfiguration {
+    /// The nickname of the bot in the guild.
     #[serde(default = "default_nickname")]
+    #[schemars(length(max = 32))]
     pub nickname: String,
+    /// The timezone used when displaying dates and times.
     #[serde(default = "default_timezone", deserialize_with = "deserialize_timezone")]
+    #[schemars(with = "tz::Timezone")]
     pub timezone: tz::Timezone,
+    /// Whether to tell users who try to use commands in direct messages that they cannot.
     #[serde(default = "default_dm_cant_use_cmd")]
     pub dmCannotUseCommand: bool
 }
 
+impl Default for GuildConfiguration {
+    fn default() -> Self {
+        Self {
+            nickname: default_nickname(),
+            timezone: default_timezone(),
+            dmCannotUseCommand: default_dm_cant_use_cmd()
+        }
+    }
+}
+
 fn default_nickname() -> String {
     String::from("HarTex")
 }
@@ -4,14 +4,30 @@
 
 use std::fmt::Formatter;
 
-use serde::de::{
-    Error,
-    Visitor
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{
+        InstanceType,
+        Metadata,
+        Schema,
+        SchemaObject
+    },
+    JsonSchema
+};
+
+use serde::{
+    de::{
+        Error,
+        Visitor
+    },
+    Serialize,
+    Serializer
 };
 
 /// # Enum `Timezone`
 ///
 /// Represents a timezone.
+#[derive(Debug, Eq, PartialEq)]
 pub enum Timezone {
     /// # Enum Variant `Timezone::AsiaHongKong`
     ///
@@ -24,6 +40,49 @@ pub enum Timezone {
     UTC
 }
 
+impl Timezone {
+    /// # Instance Method `Timezone::name`
+    ///
+    /// Returns the name of the timezone, as it is written in the configuration.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::AsiaHongKong => "Asia/Hong_Kong",
+            Self::UTC => "UTC"
+        }
+    }
+}
+
+impl Serialize for Timezone {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl JsonSchema for Timezone {
+    fn schema_name() -> String {
+        String::from("Timezone")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        Schema::Object(SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(String::from("A timezone.")),
+                ..Default::default()
+            })),
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(TIMEZONES.iter().map(|timezone| (*timezone).into()).collect()),
+            ..Default::default()
+        })
+    }
+}
+
+/// # Constant `TIMEZONES`
+///
+/// The names of the supported timezones.
+pub(crate) const TIMEZONES: &[&str] = &["Asia/Hong_Kong", "UTC"];
+
 /// # Struct `TimezoneDeserializeStringVisitor`
 ///
 /// A `String` visitor for deserializing a `Timezone`.
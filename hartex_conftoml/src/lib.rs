@@ -4,44 +4,275 @@
 //! configuration for HarTex Discord bot.
 
 #![allow(non_snake_case)]
+#![feature(format_args_capture)]
 
-use serde::Deserialize;
+use schemars::JsonSchema;
 
-use hartex_core::error::{
-    HarTexError,
-    HarTexResult
+use serde::{
+    Deserialize,
+    Serialize
 };
 
-use hartex_logging::Logger;
+use hartex_core::{
+    discord::model::id::UserId,
+    error::{
+        HarTexError,
+        HarTexResult
+    }
+};
+
+use hartex_logging::{
+    error,
+    info,
+    warn
+};
 
 pub mod dashacc;
 pub mod guildconf;
+pub mod migration;
 pub mod nightly;
+pub mod schema;
+pub mod template;
+pub mod validation;
 
 /// # Struct `TomlConfig`
 ///
 /// Represents the top-level configuration, all other configuration branches from here.
-#[derive(Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[schemars(title = "HarTex Guild Configuration", description = "The configuration of HarTex in a guild.")]
 pub struct TomlConfig {
+    /// The version of the configuration format.
+    pub version: u32,
+    /// The users who may manage the configuration of the guild.
     pub DashboardAccess: Vec<dashacc::DashboardAccess>,
+    /// General configuration of the bot in the guild.
     pub GuildConfiguration: guildconf::GuildConfiguration,
+    /// Opt-in features that are in the testing phase.
     #[serde(default)]
     pub NightlyFeatures: nightly::NightlyFeatures
 }
 
+impl TomlConfig {
+    /// # Instance Method `TomlConfig::access_level`
+    ///
+    /// Returns the access level of a user to the configuration, or `None` if the user does not
+    /// have dashboard access. If the user is listed more than once, the highest access level
+    /// applies.
+    ///
+    /// ## Parameters
+    /// - `user_id`, type `UserId`: the user to return the access level of
+    pub fn access_level(&self, user_id: UserId) -> Option<dashacc::AccessLevel> {
+        self.DashboardAccess
+            .iter()
+            .filter(|access| access.userId == user_id)
+            .map(|access| access.accessLevel)
+            .max()
+    }
+
+    /// # Instance Method `TomlConfig::can_view_config`
+    ///
+    /// Returns whether a user may view the configuration.
+    ///
+    /// ## Parameters
+    /// - `user_id`, type `UserId`: the user to check
+    pub fn can_view_config(&self, user_id: UserId) -> bool {
+        self.access_level(user_id).is_some()
+    }
+
+    /// # Instance Method `TomlConfig::can_edit_config`
+    ///
+    /// Returns whether a user may edit the configuration; whether the user may also change the
+    /// dashboard access is checked by `TomlConfig::can_replace_with`.
+    ///
+    /// ## Parameters
+    /// - `user_id`, type `UserId`: the user to check
+    pub fn can_edit_config(&self, user_id: UserId) -> bool {
+        self.access_level(user_id) >= Some(dashacc::AccessLevel::Editor)
+    }
+
+    /// # Instance Method `TomlConfig::can_replace_with`
+    ///
+    /// Returns whether a user may replace the configuration with a new configuration: editors may
+    /// change anything but the dashboard access, admins may also change the dashboard access of
+    /// users who are not owners, and owners may change everything.
+    ///
+    /// ## Parameters
+    /// - `user_id`, type `UserId`: the user to check
+    /// - `new`, type `&TomlConfig`: the new configuration
+    pub fn can_replace_with(&self, user_id: UserId, new: &TomlConfig) -> bool {
+        let level = match self.access_level(user_id) {
+            Some(level) if level >= dashacc::AccessLevel::Editor => level,
+            _ => return false
+        };
+
+        let access = |config: &TomlConfig, owners: bool| {
+            let mut entries = config.DashboardAccess
+                .iter()
+                .filter(|access| (access.accessLevel == dashacc::AccessLevel::Owner) == owners)
+                .map(|access| (access.userId.0, access.accessLevel))
+                .collect::<Vec<_>>();
+            entries.sort_unstable();
+            entries.dedup();
+
+            entries
+        };
+
+        let owners_changed = access(self, true) != access(new, true);
+        let others_changed = access(self, false) != access(new, false);
+
+        match level {
+            dashacc::AccessLevel::Owner => true,
+            dashacc::AccessLevel::Admin => !owners_changed,
+            _ => !owners_changed && !others_changed
+        }
+    }
+}
+
+/// # Struct `MigratedConfig`
+///
+/// A configuration deserialized from TOML, along with the migrations that have been applied to
+/// upgrade it to the current version and the upgraded TOML, so that it can be persisted.
+pub struct MigratedConfig {
+    pub config: TomlConfig,
+    pub report: migration::MigrationReport,
+    pub source: String
+}
+
+/// # Function `from_string`
+///
+/// Upgrades, validates and deserializes a TOML configuration; see `from_string_migrated`.
+///
+/// ## Parameters
+/// - `input`, type `String`: the TOML configuration to deserialize
 pub fn from_string(input: String) -> HarTexResult<TomlConfig> {
-    Ok(match toml::from_str(input.as_str()) {
+    Ok(from_string_migrated(input)?.config)
+}
+
+/// # Function `from_string_migrated`
+///
+/// Upgrades a TOML configuration to the current version, then validates and deserializes it.
+/// Applied migrations and warnings found by the validation are logged; if any error is found, the
+/// whole validation report is logged and returned as the error.
+///
+/// ## Parameters
+/// - `input`, type `String`: the TOML configuration to deserialize
+pub fn from_string_migrated(input: String) -> HarTexResult<MigratedConfig> {
+    let (input, migrations) = migration::migrate_source(&input)?;
+
+    if !migrations.is_empty() {
+        info!("upgraded configuration to version {version}:\n{migrations}", version = migration::CURRENT_VERSION);
+    }
+
+    let report = validation::validate(&input);
+
+    for warning in report.warnings() {
+        warn!("configuration {warning}");
+    }
+
+    if report.has_errors() {
+        error!("invalid configuration:\n{report}");
+
+        return Err(HarTexError::Custom {
+            message: report.to_string()
+        });
+    }
+
+    let config = match toml::from_str(input.as_str()) {
         Ok(config) => config,
         Err(error) => {
-            Logger::error(
-                &format!("failed to deserialize config: {}", error),
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+            error!("failed to deserialize config: {error}");
+
+            return Err(HarTexError::from(error))
+        }
+    };
+
+    Ok(MigratedConfig {
+        config,
+        report: migrations,
+        source: input
+    })
+}
+
+/// # Function `to_string`
+///
+/// Serializes a configuration back into TOML. Deserializing the returned TOML gives back an
+/// equal configuration, although comments and formatting of the original TOML are not preserved.
+///
+/// ## Parameters
+/// - `config`, type `&TomlConfig`: the configuration to serialize
+pub fn to_string(config: &TomlConfig) -> HarTexResult<String> {
+    Ok(match toml::to_string(config) {
+        Ok(serialized) => serialized,
+        Err(error) => {
+            error!("failed to serialize config: {error}");
 
             return Err(HarTexError::from(error))
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use hartex_core::discord::model::id::UserId;
+
+    use crate::{
+        dashacc::{
+            AccessLevel,
+            DashboardAccess
+        },
+        guildconf::{
+            tz::Timezone,
+            GuildConfiguration
+        },
+        migration,
+        nightly::NightlyFeatures,
+        template,
+        validation,
+        TomlConfig
+    };
+
+    #[test]
+    fn serialized_config_deserializes_to_the_same_config() {
+        let config = TomlConfig {
+            version: migration::CURRENT_VERSION,
+            DashboardAccess: vec![
+                DashboardAccess {
+                    userId: UserId(123456789012345678),
+                    accessLevel: AccessLevel::Owner
+                },
+                DashboardAccess {
+                    userId: UserId(876543210987654321),
+                    accessLevel: AccessLevel::Viewer
+                }
+            ],
+            GuildConfiguration: GuildConfiguration {
+                nickname: String::from("HarTex Beta"),
+                timezone: Timezone::AsiaHongKong,
+                dmCannotUseCommand: false
+            },
+            NightlyFeatures: NightlyFeatures {
+                threads: true,
+                localization: false
+            }
+        };
+
+        let serialized = crate::to_string(&config).unwrap();
+
+        assert_eq!(crate::from_string(serialized).unwrap(), config);
+    }
+
+    #[test]
+    fn default_config_template_parses() {
+        let config = crate::from_string(template::default_config_template()).unwrap();
+
+        assert_eq!(config.version, migration::CURRENT_VERSION);
+        assert!(config.DashboardAccess.is_empty());
+    }
+
+    #[test]
+    fn default_config_template_passes_validation() {
+        let report = validation::validate(&template::default_config_template());
+
+        assert!(!report.has_errors(), "{}", report.to_discord_message());
+    }
+}
@@ -0,0 +1,343 @@
+//! # The `migration` Module
+//!
+//! This module implements the migrations of the TOML configuration between its versions. Every
+//! configuration carries a `version` key; configurations written for older versions (including
+//! those written before the key was introduced, which are considered version `0`) are upgraded
+//! step by step to the current version before they are validated and deserialized.
+//!
+//! A migration is added with every change to the configuration format that would otherwise break
+//! existing configurations, such as renamed keys or restructured sections, and bumps
+//! `CURRENT_VERSION`.
+
+use std::fmt::{
+    self,
+    Display,
+    Formatter
+};
+
+use toml::{
+    value::Table,
+    Value
+};
+
+use hartex_core::error::{
+    HarTexError,
+    HarTexResult
+};
+
+/// # Constant `CURRENT_VERSION`
+///
+/// The current version of the configuration format.
+pub const CURRENT_VERSION: u32 = 2;
+
+/// # Constant `MIGRATIONS`
+///
+/// The migrations between the versions of the configuration format, in order; the migration at
+/// index `n` upgrades a configuration from version `n` to version `n + 1`.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "add the `version` key",
+        migrate: |_| Ok(())
+    },
+    Migration {
+        description: "replace numeric dashboard access levels with named access levels (0 = viewer, 1 = editor, 2 = admin, 3 and above = owner)",
+        migrate: migrate_named_access_levels
+    }
+];
+
+/// # Struct `Migration`
+///
+/// A migration of the configuration format from a version to the next.
+struct Migration {
+    description: &'static str,
+    migrate: fn(&mut Table) -> Result<(), String>
+}
+
+/// # Function `migrate_named_access_levels`
+///
+/// Migrates a configuration from version 1 to version 2, replacing the numeric access levels of
+/// the `DashboardAccess` entries with named access levels.
+fn migrate_named_access_levels(document: &mut Table) -> Result<(), String> {
+    let entries = match document.get_mut("DashboardAccess") {
+        Some(Value::Array(entries)) => entries,
+        _ => return Ok(())
+    };
+
+    for entry in entries.iter_mut().filter_map(Value::as_table_mut) {
+        let name = match entry.get("accessLevel") {
+            Some(Value::Integer(0)) => "viewer",
+            Some(Value::Integer(1)) => "editor",
+            Some(Value::Integer(2)) => "admin",
+            Some(Value::Integer(3..=255)) => "owner",
+            // anything else is left for the validation to report
+            _ => continue
+        };
+
+        entry.insert(String::from("accessLevel"), Value::String(String::from(name)));
+    }
+
+    Ok(())
+}
+
+/// # Struct `AppliedMigration`
+///
+/// A migration that has been applied to a configuration.
+#[derive(Clone, Debug)]
+pub struct AppliedMigration {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub description: &'static str
+}
+
+/// # Struct `MigrationReport`
+///
+/// The migrations that have been applied to a configuration, in the order they were applied.
+#[derive(Clone, Debug, Default)]
+pub struct MigrationReport {
+    pub applied: Vec<AppliedMigration>
+}
+
+impl MigrationReport {
+    /// # Instance Method `MigrationReport::is_empty`
+    ///
+    /// Returns whether no migration has been applied, i.e. the configuration was already of the
+    /// current version.
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty()
+    }
+}
+
+impl Display for MigrationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, migration) in self.applied.iter().enumerate() {
+            if index != 0 {
+                writeln!(f)?;
+            }
+
+            write!(
+                f,
+                "v{from} -> v{to}: {description}",
+                from = migration.from_version,
+                to = migration.to_version,
+                description = migration.description
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// # Function `migrate`
+///
+/// Upgrades a configuration document to the current version in place, returning the migrations
+/// that have been applied.
+///
+/// Documents whose `version` is not an integer or is newer than the current version are left
+/// unchanged, for the validation to report.
+///
+/// ## Parameters
+/// - `document`, type `&mut Table`: the configuration document to upgrade
+pub fn migrate(document: &mut Table) -> HarTexResult<MigrationReport> {
+    let mut version = match document.get("version") {
+        None => 0,
+        Some(Value::Integer(version)) if (0..=CURRENT_VERSION as i64).contains(version) => *version as u32,
+        Some(_) => return Ok(MigrationReport::default())
+    };
+    let mut report = MigrationReport::default();
+
+    while version < CURRENT_VERSION {
+        let migration = &MIGRATIONS[version as usize];
+
+        if let Err(message) = (migration.migrate)(document) {
+            return Err(HarTexError::Custom {
+                message: format!("failed to migrate configuration from version {version} to version {next}: {message}", next = version + 1)
+            });
+        }
+
+        document.insert(String::from("version"), Value::Integer(i64::from(version + 1)));
+        report.applied.push(AppliedMigration {
+            from_version: version,
+            to_version: version + 1,
+            description: migration.description
+        });
+
+        version += 1;
+    }
+
+    Ok(report)
+}
+
+/// # Function `migrate_source`
+///
+/// Upgrades a TOML configuration to the current version, returning the upgraded TOML and the
+/// migrations that have been applied. If no migration has been applied, the TOML is returned
+/// unchanged; otherwise it is serialized again from the upgraded document, and comments and
+/// formatting are not preserved.
+///
+/// A TOML configuration that cannot be parsed is returned unchanged, for the validation to report.
+///
+/// ## Parameters
+/// - `source`, type `&str`: the TOML configuration to upgrade
+pub fn migrate_source(source: &str) -> HarTexResult<(String, MigrationReport)> {
+    let mut document = match toml::from_str::<Table>(source) {
+        Ok(document) => document,
+        Err(_) => return Ok((source.to_string(), MigrationReport::default()))
+    };
+
+    let report = migrate(&mut document)?;
+    if report.is_empty() {
+        return Ok((source.to_string(), report));
+    }
+
+    // serializing the document as a value emits the plain keys before the tables, as TOML requires
+    Ok((toml::to_string(&Value::Table(document))?, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use toml::{
+        value::Table,
+        Value
+    };
+
+    use super::CURRENT_VERSION;
+
+    fn parse(source: &str) -> Table {
+        toml::from_str(source).unwrap()
+    }
+
+    fn access_levels(document: &Table) -> Vec<Value> {
+        document["DashboardAccess"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["accessLevel"].clone())
+            .collect()
+    }
+
+    #[test]
+    fn upgrades_a_configuration_without_version() {
+        let mut document = parse(r#"
+            [[DashboardAccess]]
+            userId = 1000
+            accessLevel = 3
+        "#);
+
+        let report = super::migrate(&mut document).unwrap();
+
+        assert_eq!(document["version"], Value::Integer(i64::from(CURRENT_VERSION)));
+        assert_eq!(access_levels(&document), vec![Value::String(String::from("owner"))]);
+        assert_eq!(report.applied.len(), 2);
+    }
+
+    #[test]
+    fn upgrades_a_version_1_configuration() {
+        let mut document = parse(r#"
+            version = 1
+
+            [[DashboardAccess]]
+            userId = 1000
+            accessLevel = 1
+        "#);
+
+        let report = super::migrate(&mut document).unwrap();
+
+        assert_eq!(document["version"], Value::Integer(2));
+        assert_eq!(access_levels(&document), vec![Value::String(String::from("editor"))]);
+        assert_eq!(report.applied.len(), 1);
+        assert_eq!(report.applied[0].from_version, 1);
+    }
+
+    #[test]
+    fn converts_numeric_access_levels_to_names() {
+        let mut document = parse(r#"
+            version = 1
+
+            [[DashboardAccess]]
+            userId = 1
+            accessLevel = 0
+
+            [[DashboardAccess]]
+            userId = 2
+            accessLevel = 1
+
+            [[DashboardAccess]]
+            userId = 3
+            accessLevel = 2
+
+            [[DashboardAccess]]
+            userId = 4
+            accessLevel = 3
+
+            [[DashboardAccess]]
+            userId = 5
+            accessLevel = 255
+
+            [[DashboardAccess]]
+            userId = 6
+            accessLevel = 256
+
+            [[DashboardAccess]]
+            userId = 7
+            accessLevel = "admin"
+        "#);
+
+        super::migrate(&mut document).unwrap();
+
+        // the levels that cannot be converted are left for the validation to report
+        assert_eq!(access_levels(&document), vec![
+            Value::String(String::from("viewer")),
+            Value::String(String::from("editor")),
+            Value::String(String::from("admin")),
+            Value::String(String::from("owner")),
+            Value::String(String::from("owner")),
+            Value::Integer(256),
+            Value::String(String::from("admin"))
+        ]);
+    }
+
+    #[test]
+    fn reports_the_applied_migrations_in_order() {
+        let (source, report) = super::migrate_source("").unwrap();
+
+        let steps = report.applied
+            .iter()
+            .map(|migration| (migration.from_version, migration.to_version))
+            .collect::<Vec<_>>();
+
+        assert_eq!(steps, vec![(0, 1), (1, 2)]);
+        assert_eq!(
+            report.to_string(),
+            format!(
+                "v0 -> v1: {first}\nv1 -> v2: {second}",
+                first = report.applied[0].description,
+                second = report.applied[1].description
+            )
+        );
+        assert_eq!(parse(&source)["version"], Value::Integer(2));
+    }
+
+    #[test]
+    fn leaves_a_current_configuration_unchanged() {
+        let source = "# comments are kept\nversion = 2\n";
+
+        let (migrated, report) = super::migrate_source(source).unwrap();
+
+        assert!(report.is_empty());
+        assert_eq!(migrated, source);
+    }
+
+    #[test]
+    fn rejects_a_newer_version() {
+        let source = format!("version = {newer}\n", newer = CURRENT_VERSION + 1);
+
+        let (migrated, report) = super::migrate_source(&source).unwrap();
+
+        assert!(report.is_empty());
+        assert_eq!(migrated, source);
+
+        let error = crate::from_string(source).unwrap_err();
+
+        assert!(format!("{error:?}").contains("is not supported"), "{error:?}");
+    }
+}
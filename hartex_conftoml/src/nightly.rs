@@ -4,22 +4,87 @@
 //! phase.
 //!
 //! This API is very unstable and may change rapidly as features are added into the bot.
+//!
+//! A new nightly feature is added as a field of `NightlyFeatures` and a variant of
+//! `NightlyFeature`; commands and event handlers then declare the variant they require, and are
+//! skipped in guilds that have not opted in to it.
+
+use std::fmt::{
+    self,
+    Display,
+    Formatter
+};
 
-use serde::Deserialize;
+use schemars::JsonSchema;
+
+use serde::{
+    Deserialize,
+    Serialize
+};
 
 /// # Struct `NightlyFeatures`
 ///
 /// The opt-in nightly features that the bot provides.
-#[derive(Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[schemars(description = "Opt-in features that are in the testing phase; they may change or be removed at any time.")]
 pub struct NightlyFeatures {
-    // Experimental Support for the Discord Threads API
+    /// Experimental support for the Discord threads API.
     #[serde(default = "default_feature_enabled")]
     pub threads: bool,
-    // Experimental Support for localization Facilities, i.e. timezones, languages
+    /// Experimental support for localization facilities, i.e. timezones, languages.
     #[serde(default = "default_feature_enabled")]
     pub localization: bool
 }
 
+impl NightlyFeatures {
+    /// # Instance Method `NightlyFeatures::is_enabled`
+    ///
+    /// Returns whether the guild has opted in to a nightly feature.
+    ///
+    /// ## Parameters
+    /// - `feature`, type `NightlyFeature`: the nightly feature to check
+    pub fn is_enabled(&self, feature: NightlyFeature) -> bool {
+        match feature {
+            NightlyFeature::Threads => self.threads,
+            NightlyFeature::Localization => self.localization
+        }
+    }
+}
+
+/// # Enum `NightlyFeature`
+///
+/// A nightly feature that commands and event handlers may require.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NightlyFeature {
+    /// # Enum Variant `NightlyFeature::Threads`
+    ///
+    /// Experimental support for the Discord threads API.
+    Threads,
+
+    /// # Enum Variant `NightlyFeature::Localization`
+    ///
+    /// Experimental support for localization facilities.
+    Localization
+}
+
+impl NightlyFeature {
+    /// # Instance Method `NightlyFeature::name`
+    ///
+    /// Returns the name of the nightly feature, as it is written in the configuration.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Threads => "threads",
+            Self::Localization => "localization"
+        }
+    }
+}
+
+impl Display for NightlyFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 pub fn default_feature_enabled() -> bool {
     false
 }
@@ -0,0 +1,23 @@
+//! # The `schema` Module
+//!
+//! This module generates a JSON Schema of the TOML configuration from the configuration models,
+//! including the default values, the allowed values of enumerations (such as timezones) and
+//! descriptions of every key.
+//!
+//! Editors with TOML support, such as Visual Studio Code with the Even Better TOML extension, can
+//! use the schema for completion and validation while writing a configuration; for example, by
+//! adding a `#:schema ./hartex.schema.json` directive at the top of the configuration file. The
+//! schema is written to a file with the `hartex_schema` binary of this crate.
+
+use schemars::schema_for;
+
+use crate::TomlConfig;
+
+/// # Function `json_schema`
+///
+/// Returns the JSON Schema of the TOML configuration, pretty-printed.
+pub fn json_schema() -> String {
+    // serializing a schema only fails if the schema contains non-string map keys, which it never
+    // does; unwrapping here is fine
+    serde_json::to_string_pretty(&schema_for!(TomlConfig)).unwrap()
+}
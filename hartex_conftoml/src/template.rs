@@ -0,0 +1,68 @@
+//! # The `template` Module
+//!
+//! This module contains the default configuration template, a fully commented configuration file
+//! that guilds can start their configuration from.
+
+use crate::migration;
+
+/// # Constant `DEFAULT_CONFIG_TEMPLATE`
+///
+/// The default configuration template. Every key is documented, and every optional key is set to
+/// its default value; `{version}` is replaced with the current version of the configuration format.
+const DEFAULT_CONFIG_TEMPLATE: &str = r#"# HarTex Guild Configuration
+#
+# This is the default configuration of HarTex. Keys marked as optional may be removed, in which
+# case their default values (shown below) are used.
+
+# The version of the configuration format; configurations of older versions are upgraded
+# automatically.
+version = {version}
+
+# Dashboard Access
+#
+# The users who may manage the configuration of the guild. While no user is listed, only the guild
+# owner may manage the configuration.
+#
+# The access level of a user is one of the following; each level includes the permissions of the
+# levels below it:
+#   - "viewer": may view the configuration;
+#   - "editor": may edit the configuration, except for the dashboard access;
+#   - "admin":  may edit the configuration, including the dashboard access of users who are not
+#               owners;
+#   - "owner":  may edit the whole configuration, including the dashboard access of other owners.
+#
+# To grant access to users, replace the empty list below with one entry per user, for example:
+#
+# [[DashboardAccess]]
+# userId = 123456789012345678   # the id of the user
+# accessLevel = "editor"        # the access level of the user
+DashboardAccess = []
+
+# Guild Configuration
+#
+# General configuration of the bot in the guild.
+[GuildConfiguration]
+# (optional) The nickname of the bot in the guild; at most 32 characters.
+nickname = "HarTex"
+# (optional) The timezone used when displaying dates and times; one of "Asia/Hong_Kong" or "UTC".
+timezone = "UTC"
+# (optional) Whether to tell users who try to use commands in direct messages that they cannot.
+dmCannotUseCommand = true
+
+# Nightly Features
+#
+# (optional) Opt-in features that are in the testing phase; they may change or be removed at any
+# time.
+[NightlyFeatures]
+# (optional) Experimental support for the Discord threads API.
+threads = false
+# (optional) Experimental support for localization facilities, i.e. timezones and languages.
+localization = false
+"#;
+
+/// # Function `default_config_template`
+///
+/// Returns the default configuration template.
+pub fn default_config_template() -> String {
+    DEFAULT_CONFIG_TEMPLATE.replace("{version}", &migration::CURRENT_VERSION.to_string())
+}
@@ -0,0 +1,793 @@
+//! # The `validation` Module
+//!
+//! This module implements a validation pass over TOML configuration, which reports every problem
+//! found in the configuration with its location (line and column), the path of the offending key,
+//! an explanation and, where possible, a suggestion of how to fix it.
+//!
+//! The reports are rendered both for logging (with `Display`) and as a Discord-friendly message
+//! (with `ValidationReport::to_discord_message`).
+
+use std::fmt::{
+    self,
+    Display,
+    Formatter
+};
+
+use toml::Value;
+
+use crate::{
+    dashacc::AccessLevel,
+    guildconf::tz,
+    migration
+};
+
+/// # Constant `MAX_DISCORD_MESSAGE_LENGTH`
+///
+/// The maximum length of a rendered Discord message, leaving room for the surrounding text of the
+/// message the report is embedded in.
+const MAX_DISCORD_MESSAGE_LENGTH: usize = 1800;
+
+/// # Constant `MAX_SUGGESTION_DISTANCE`
+///
+/// The maximum edit distance for a known name to be suggested in place of an unknown name.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// # Constant `ROOT_KEYS`
+///
+/// The known keys of the top-level configuration.
+const ROOT_KEYS: &[&str] = &["version", "DashboardAccess", "GuildConfiguration", "NightlyFeatures"];
+
+/// # Constant `DASHBOARD_ACCESS_KEYS`
+///
+/// The known keys of a `DashboardAccess` entry.
+const DASHBOARD_ACCESS_KEYS: &[&str] = &["userId", "accessLevel"];
+
+/// # Constant `GUILD_CONFIGURATION_KEYS`
+///
+/// The known keys of the `GuildConfiguration` table.
+const GUILD_CONFIGURATION_KEYS: &[&str] = &["nickname", "timezone", "dmCannotUseCommand"];
+
+/// # Constant `NIGHTLY_FEATURES_KEYS`
+///
+/// The known keys of the `NightlyFeatures` table.
+const NIGHTLY_FEATURES_KEYS: &[&str] = &["threads", "localization"];
+
+/// # Enum `Severity`
+///
+/// The severity of a validation issue.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Severity {
+    /// # Enum Variant `Severity::Error`
+    ///
+    /// The configuration cannot be used.
+    Error,
+
+    /// # Enum Variant `Severity::Warning`
+    ///
+    /// The configuration can be used, but probably does not do what is intended; for example, a
+    /// key that is not known and is therefore ignored.
+    Warning
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning")
+        }
+    }
+}
+
+/// # Struct `ValidationIssue`
+///
+/// A problem found in a configuration.
+#[derive(Clone, Debug)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// The location of the problem as a 1-based `(line, column)` pair, if it could be located.
+    pub location: Option<(usize, usize)>,
+    /// The path of the offending key, for example `GuildConfiguration.timezone`; empty if the
+    /// problem concerns the whole configuration.
+    pub key_path: String,
+    pub explanation: String,
+    pub suggestion: Option<String>
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{severity}: {explanation}", severity = self.severity, explanation = self.explanation)?;
+
+        if let Some((line, column)) = self.location {
+            write!(f, "\n  --> line {line}, column {column}")?;
+        }
+
+        if !self.key_path.is_empty() {
+            write!(f, "\n  at `{key_path}`", key_path = self.key_path)?;
+        }
+
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\n  help: {suggestion}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// # Struct `ValidationReport`
+///
+/// The result of validating a configuration: all the problems found, in the order they were
+/// found.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>
+}
+
+impl ValidationReport {
+    /// # Instance Method `ValidationReport::has_errors`
+    ///
+    /// Returns whether the report contains any error; a configuration with only warnings can
+    /// still be used.
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// # Instance Method `ValidationReport::errors`
+    ///
+    /// Returns the errors in the report.
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// # Instance Method `ValidationReport::warnings`
+    ///
+    /// Returns the warnings in the report.
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    /// # Instance Method `ValidationReport::to_discord_message`
+    ///
+    /// Renders the report as a Discord-friendly message; issues that do not fit in a message are
+    /// summarized at the end.
+    pub fn to_discord_message(&self) -> String {
+        let mut message = String::new();
+
+        for (index, issue) in self.issues.iter().enumerate() {
+            let mut rendered = match issue.severity {
+                Severity::Error => String::from(":x: **Error**"),
+                Severity::Warning => String::from(":warning: **Warning**")
+            };
+
+            if let Some((line, column)) = issue.location {
+                rendered.push_str(&format!(" at line {line}, column {column}"));
+            }
+
+            if !issue.key_path.is_empty() {
+                rendered.push_str(&format!(" (`{key_path}`)", key_path = issue.key_path));
+            }
+
+            rendered.push_str(&format!(": {explanation}\n", explanation = issue.explanation));
+
+            if let Some(suggestion) = &issue.suggestion {
+                rendered.push_str(&format!("> {suggestion}\n"));
+            }
+
+            if message.len() + rendered.len() > MAX_DISCORD_MESSAGE_LENGTH {
+                message.push_str(&format!("... and {remaining} more", remaining = self.issues.len() - index));
+                break;
+            }
+
+            message.push_str(&rendered);
+        }
+
+        message
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, issue) in self.issues.iter().enumerate() {
+            if index != 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// # Function `validate`
+///
+/// Validates a TOML configuration, reporting every problem found in it.
+///
+/// ## Parameters
+/// - `source`, type `&str`: the TOML configuration to validate
+pub fn validate(source: &str) -> ValidationReport {
+    let mut validator = Validator {
+        source,
+        report: ValidationReport::default()
+    };
+
+    match toml::from_str::<Value>(source) {
+        Ok(Value::Table(root)) => validator.validate_root(&root),
+        Ok(_) => unreachable!("a TOML document is always a table"),
+        Err(error) => validator.push_toml_error(&error)
+    }
+
+    // any problem that the validation pass above does not know about is still reported by
+    // deserializing the configuration itself
+    if !validator.report.has_errors() {
+        if let Err(error) = toml::from_str::<crate::TomlConfig>(source) {
+            validator.push_toml_error(&error);
+        }
+    }
+
+    validator.report
+}
+
+/// # Enum `Segment`
+///
+/// A segment of the path of a key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Segment {
+    Key(String),
+    Index(usize)
+}
+
+/// # Function `render_path`
+///
+/// Renders the path of a key, for example `DashboardAccess[0].userId`.
+///
+/// ## Parameters
+/// - `path`, type `&[Segment]`: the path to render
+fn render_path(path: &[Segment]) -> String {
+    let mut rendered = String::new();
+
+    for segment in path {
+        match segment {
+            Segment::Key(key) => {
+                if !rendered.is_empty() {
+                    rendered.push('.');
+                }
+
+                rendered.push_str(key);
+            }
+            Segment::Index(index) => rendered.push_str(&format!("[{index}]"))
+        }
+    }
+
+    rendered
+}
+
+/// # Struct `Validator`
+///
+/// The state of a validation pass.
+struct Validator<'source> {
+    source: &'source str,
+    report: ValidationReport
+}
+
+impl<'source> Validator<'source> {
+    /// # Instance Method `Validator::validate_root`
+    ///
+    /// Validates the top-level configuration.
+    fn validate_root(&mut self, root: &toml::value::Table) {
+        self.check_unknown_keys(root, &[], ROOT_KEYS);
+
+        match root.get("version") {
+            Some(Value::Integer(version)) if (0..=i64::from(migration::CURRENT_VERSION)).contains(version) => (),
+            Some(Value::Integer(version)) => self.push(
+                Severity::Error,
+                &[Segment::Key(String::from("version"))],
+                format!("configuration version {version} is not supported"),
+                Some(format!("the latest supported version is {current}", current = migration::CURRENT_VERSION))
+            ),
+            Some(other) => self.push_type_mismatch(&[Segment::Key(String::from("version"))], "an integer", other),
+            None => self.push_missing_key(&[], "version", &format!("add `version = {current}` at the top of the configuration", current = migration::CURRENT_VERSION))
+        }
+
+        match root.get("DashboardAccess") {
+            Some(Value::Array(entries)) => {
+                for (index, entry) in entries.iter().enumerate() {
+                    let path = vec![Segment::Key(String::from("DashboardAccess")), Segment::Index(index)];
+
+                    match entry {
+                        Value::Table(entry) => self.validate_dashboard_access(entry, &path),
+                        other => self.push_type_mismatch(&path, "a table", other)
+                    }
+                }
+            }
+            Some(other) => self.push_type_mismatch(&[Segment::Key(String::from("DashboardAccess"))], "an array of tables", other),
+            None => self.push_missing_key(&[], "DashboardAccess", "add at least one `[[DashboardAccess]]` entry with a `userId` and an `accessLevel`")
+        }
+
+        match root.get("GuildConfiguration") {
+            Some(Value::Table(table)) => self.validate_guild_configuration(table),
+            Some(other) => self.push_type_mismatch(&[Segment::Key(String::from("GuildConfiguration"))], "a table", other),
+            None => self.push_missing_key(&[], "GuildConfiguration", "add a `[GuildConfiguration]` table")
+        }
+
+        match root.get("NightlyFeatures") {
+            Some(Value::Table(table)) => {
+                let path = vec![Segment::Key(String::from("NightlyFeatures"))];
+                self.check_unknown_keys(table, &path, NIGHTLY_FEATURES_KEYS);
+
+                for key in NIGHTLY_FEATURES_KEYS {
+                    self.check_boolean(table, &path, key);
+                }
+            }
+            Some(other) => self.push_type_mismatch(&[Segment::Key(String::from("NightlyFeatures"))], "a table", other),
+            None => ()
+        }
+    }
+
+    /// # Instance Method `Validator::validate_dashboard_access`
+    ///
+    /// Validates a `DashboardAccess` entry.
+    fn validate_dashboard_access(&mut self, entry: &toml::value::Table, path: &[Segment]) {
+        self.check_unknown_keys(entry, path, DASHBOARD_ACCESS_KEYS);
+
+        match entry.get("userId") {
+            Some(Value::Integer(id)) if *id > 0 => (),
+            Some(Value::Integer(_)) => self.push(
+                Severity::Error,
+                &with_key(path, "userId"),
+                String::from("a user id must be a positive integer"),
+                Some(String::from("copy the id of the user with developer mode enabled in Discord"))
+            ),
+            Some(other) => self.push_type_mismatch(&with_key(path, "userId"), "an integer", other),
+            None => self.push_missing_key(path, "userId", "add the id of the user to grant dashboard access to")
+        }
+
+        match entry.get("accessLevel") {
+            Some(Value::String(level)) if AccessLevel::NAMES.contains(&level.as_str()) => (),
+            Some(Value::String(level)) => {
+                let suggestion = match closest_matches(level, AccessLevel::NAMES).as_slice() {
+                    [] => format!("use one of the access levels: {levels}", levels = quote_all(AccessLevel::NAMES)),
+                    matches => format!("did you mean {matches}?", matches = quote_all(matches))
+                };
+
+                self.push(
+                    Severity::Error,
+                    &with_key(path, "accessLevel"),
+                    format!("unknown access level `{level}`"),
+                    Some(suggestion)
+                );
+            }
+            Some(other) => self.push_type_mismatch(&with_key(path, "accessLevel"), "a string", other),
+            None => self.push_missing_key(path, "accessLevel", "add the access level to grant to the user")
+        }
+    }
+
+    /// # Instance Method `Validator::validate_guild_configuration`
+    ///
+    /// Validates the `GuildConfiguration` table.
+    fn validate_guild_configuration(&mut self, table: &toml::value::Table) {
+        let path = vec![Segment::Key(String::from("GuildConfiguration"))];
+        self.check_unknown_keys(table, &path, GUILD_CONFIGURATION_KEYS);
+
+        match table.get("nickname") {
+            Some(Value::String(nickname)) if nickname.chars().count() > 32 => self.push(
+                Severity::Error,
+                &with_key(&path, "nickname"),
+                format!("nickname `{nickname}` is longer than 32 characters"),
+                Some(String::from("use a nickname of at most 32 characters, the limit set by Discord"))
+            ),
+            Some(Value::String(_)) | None => (),
+            Some(other) => self.push_type_mismatch(&with_key(&path, "nickname"), "a string", other)
+        }
+
+        match table.get("timezone") {
+            Some(Value::String(timezone)) if tz::TIMEZONES.contains(&timezone.as_str()) => (),
+            Some(Value::String(timezone)) => {
+                let suggestion = match closest_matches(timezone, tz::TIMEZONES).as_slice() {
+                    [] => format!("use one of the supported timezones: {supported}", supported = quote_all(tz::TIMEZONES)),
+                    matches => format!("did you mean {matches}?", matches = quote_all(matches))
+                };
+
+                self.push(
+                    Severity::Error,
+                    &with_key(&path, "timezone"),
+                    format!("unknown timezone `{timezone}`"),
+                    Some(suggestion)
+                );
+            }
+            Some(other) => self.push_type_mismatch(&with_key(&path, "timezone"), "a string", other),
+            None => ()
+        }
+
+        self.check_boolean(table, &path, "dmCannotUseCommand");
+    }
+
+    /// # Instance Method `Validator::check_boolean`
+    ///
+    /// Checks that an optional key of a table is a boolean.
+    fn check_boolean(&mut self, table: &toml::value::Table, path: &[Segment], key: &str) {
+        match table.get(key) {
+            Some(Value::Boolean(_)) | None => (),
+            Some(other) => self.push_type_mismatch(&with_key(path, key), "a boolean (`true` or `false`)", other)
+        }
+    }
+
+    /// # Instance Method `Validator::check_unknown_keys`
+    ///
+    /// Reports a warning for every key of a table that is not known; unknown keys are ignored
+    /// when the configuration is deserialized.
+    fn check_unknown_keys(&mut self, table: &toml::value::Table, path: &[Segment], known: &[&str]) {
+        for key in table.keys() {
+            if known.contains(&key.as_str()) {
+                continue;
+            }
+
+            let suggestion = match closest_matches(key, known).as_slice() {
+                [] => format!("remove the key; the known keys here are {known}", known = quote_all(known)),
+                matches => format!("did you mean {matches}?", matches = quote_all(matches))
+            };
+
+            self.push(
+                Severity::Warning,
+                &with_key(path, key),
+                format!("unknown key `{key}` will be ignored"),
+                Some(suggestion)
+            );
+        }
+    }
+
+    /// # Instance Method `Validator::push_missing_key`
+    ///
+    /// Reports a required key that is missing from a table.
+    fn push_missing_key(&mut self, path: &[Segment], key: &str, suggestion: &str) {
+        self.push(
+            Severity::Error,
+            path,
+            format!("missing required key `{key}`"),
+            Some(String::from(suggestion))
+        );
+    }
+
+    /// # Instance Method `Validator::push_type_mismatch`
+    ///
+    /// Reports a key whose value is of an unexpected type.
+    fn push_type_mismatch(&mut self, path: &[Segment], expected: &str, found: &Value) {
+        self.push(
+            Severity::Error,
+            path,
+            format!("expected {expected}, found {found}", found = describe(found)),
+            None
+        );
+    }
+
+    /// # Instance Method `Validator::push_toml_error`
+    ///
+    /// Reports an error returned by the TOML parser or deserializer.
+    fn push_toml_error(&mut self, error: &toml::de::Error) {
+        let mut explanation = error.to_string();
+
+        // the location is reported separately
+        if let Some(index) = explanation.find(" at line ") {
+            explanation.truncate(index);
+        }
+
+        self.report.issues.push(ValidationIssue {
+            severity: Severity::Error,
+            location: error.line_col().map(|(line, column)| (line + 1, column + 1)),
+            key_path: String::new(),
+            explanation,
+            suggestion: None
+        });
+    }
+
+    /// # Instance Method `Validator::push`
+    ///
+    /// Reports an issue, locating the key in the source.
+    fn push(&mut self, severity: Severity, path: &[Segment], explanation: String, suggestion: Option<String>) {
+        self.report.issues.push(ValidationIssue {
+            severity,
+            location: locate(self.source, path),
+            key_path: render_path(path),
+            explanation,
+            suggestion
+        });
+    }
+}
+
+/// # Function `with_key`
+///
+/// Returns a path extended with a key.
+fn with_key(path: &[Segment], key: &str) -> Vec<Segment> {
+    let mut path = path.to_vec();
+    path.push(Segment::Key(String::from(key)));
+
+    path
+}
+
+/// # Function `describe`
+///
+/// Describes the type of a TOML value for error messages.
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::String(_) => "a string",
+        Value::Integer(_) => "an integer",
+        Value::Float(_) => "a float",
+        Value::Boolean(_) => "a boolean",
+        Value::Datetime(_) => "a datetime",
+        Value::Array(_) => "an array",
+        Value::Table(_) => "a table"
+    }
+}
+
+/// # Function `quote_all`
+///
+/// Renders a list of names as a comma-separated list of code spans.
+fn quote_all(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// # Function `closest_matches`
+///
+/// Returns the known names closest to an unknown name, closest first; names that are too
+/// different are not returned. The comparison is case-insensitive.
+fn closest_matches<'known>(name: &str, known: &[&'known str]) -> Vec<&'known str> {
+    let name = name.to_lowercase();
+    let mut matches = known
+        .iter()
+        .map(|candidate| (edit_distance(&name, &candidate.to_lowercase()), *candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .collect::<Vec<_>>();
+    matches.sort_by_key(|(distance, _)| *distance);
+
+    matches.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+/// # Function `edit_distance`
+///
+/// Returns the Levenshtein distance between two strings.
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right = right.chars().collect::<Vec<_>>();
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1; right.len() + 1];
+
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != *right_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        previous = current;
+    }
+
+    previous[right.len()]
+}
+
+/// # Function `locate`
+///
+/// Locates a key in the source of a configuration, returning its 1-based `(line, column)`. If the
+/// key itself cannot be found (for example, because it is missing), the location of its closest
+/// enclosing table or key is returned instead.
+///
+/// Only the TOML constructs used by configurations are understood: table headers, array of tables
+/// headers and (possibly dotted) keys.
+fn locate(source: &str, path: &[Segment]) -> Option<(usize, usize)> {
+    let mut header = Vec::<Segment>::new();
+    let mut array_counts = Vec::<(Vec<Segment>, usize)>::new();
+    let mut best: Option<(usize, (usize, usize))> = None;
+
+    let mut consider = |candidate: &[Segment], location: (usize, usize)| {
+        if candidate.len() <= path.len()
+            && path[..candidate.len()] == *candidate
+            && best.map(|(length, _)| candidate.len() > length).unwrap_or(true) {
+            best = Some((candidate.len(), location));
+        }
+    };
+
+    for (index, line) in source.lines().enumerate() {
+        let indentation = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = trimmed.strip_prefix("[[") {
+            let name = rest.split("]]").next().unwrap_or_default();
+            let keys = split_key(name);
+
+            let count = match array_counts.iter_mut().find(|(seen, _)| *seen == keys) {
+                Some((_, count)) => {
+                    *count += 1;
+                    *count
+                }
+                None => {
+                    array_counts.push((keys.clone(), 0));
+                    0
+                }
+            };
+
+            header = keys;
+            header.push(Segment::Index(count));
+            consider(&header, (index + 1, indentation + 3));
+        }
+        else if let Some(rest) = trimmed.strip_prefix('[') {
+            let name = rest.split(']').next().unwrap_or_default();
+
+            header = split_key(name);
+            consider(&header, (index + 1, indentation + 2));
+        }
+        else if let Some((key, _)) = trimmed.split_once('=') {
+            let mut full = header.clone();
+            full.extend(split_key(key));
+            consider(&full, (index + 1, indentation + 1));
+        }
+    }
+
+    best.map(|(_, location)| location)
+}
+
+/// # Function `split_key`
+///
+/// Splits a (possibly dotted and quoted) key into path segments.
+fn split_key(key: &str) -> Vec<Segment> {
+    key.split('.')
+        .map(|part| Segment::Key(String::from(part.trim().trim_matches('"').trim_matches('\''))))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use toml::Value;
+
+    use hartex_core::discord::model::id::UserId;
+
+    use crate::{
+        dashacc::{
+            AccessLevel,
+            DashboardAccess
+        },
+        template
+    };
+
+    use super::{
+        Severity,
+        ValidationIssue,
+        DASHBOARD_ACCESS_KEYS,
+        GUILD_CONFIGURATION_KEYS,
+        MAX_DISCORD_MESSAGE_LENGTH,
+        NIGHTLY_FEATURES_KEYS,
+        ROOT_KEYS
+    };
+
+    const CONFIG: &[&str] = &[
+        "version = 2",
+        "",
+        "[[DashboardAccess]]",
+        "userId = 1000",
+        "accessLevel = \"owner\"",
+        "",
+        "[[DashboardAccess]]",
+        "userId = 2000",
+        "accessLevel = \"admn\"",
+        "",
+        "[GuildConfiguration]",
+        "  timezone = \"Asia/Hong_Kon\"",
+        "  nicknam = \"HarTex\""
+    ];
+
+    fn issue_at<'report>(issues: &'report [ValidationIssue], key_path: &str) -> &'report ValidationIssue {
+        issues
+            .iter()
+            .find(|issue| issue.key_path == key_path)
+            .unwrap_or_else(|| panic!("no issue at `{key_path}` in {issues:#?}"))
+    }
+
+    fn sorted_keys(value: &Value) -> Vec<String> {
+        let mut keys = value.as_table().unwrap().keys().cloned().collect::<Vec<_>>();
+        keys.sort();
+
+        keys
+    }
+
+    fn sorted(known: &[&str]) -> Vec<String> {
+        let mut keys = known.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+        keys.sort();
+
+        keys
+    }
+
+    #[test]
+    fn locates_a_bad_value() {
+        let report = super::validate(&CONFIG.join("\n"));
+
+        assert_eq!(issue_at(&report.issues, "GuildConfiguration.timezone").location, Some((12, 3)));
+        assert_eq!(issue_at(&report.issues, "DashboardAccess[1].accessLevel").location, Some((9, 1)));
+    }
+
+    #[test]
+    fn reports_the_key_path_of_a_nested_error() {
+        let report = super::validate(&CONFIG.join("\n"));
+        let issue = issue_at(&report.issues, "DashboardAccess[1].accessLevel");
+
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.explanation, "unknown access level `admn`");
+    }
+
+    #[test]
+    fn suggests_the_closest_known_names() {
+        let report = super::validate(&CONFIG.join("\n"));
+
+        assert_eq!(
+            issue_at(&report.issues, "DashboardAccess[1].accessLevel").suggestion.as_deref(),
+            Some("did you mean `admin`?")
+        );
+        assert_eq!(
+            issue_at(&report.issues, "GuildConfiguration.timezone").suggestion.as_deref(),
+            Some("did you mean `Asia/Hong_Kong`?")
+        );
+        assert!(super::closest_matches("moderator", AccessLevel::NAMES).is_empty());
+        assert_eq!(super::edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn warns_about_unknown_keys() {
+        // the template ends with the `NightlyFeatures` table
+        let source = template::default_config_template() + "\nthred = true\n";
+        let source = source.replace("dmCannotUseCommand", "dmCannotUseCommands");
+
+        let report = super::validate(&source);
+        let issue = issue_at(&report.issues, "GuildConfiguration.dmCannotUseCommands");
+
+        assert!(!report.has_errors(), "{report}");
+        assert_eq!(issue.severity, Severity::Warning);
+        assert_eq!(issue.explanation, "unknown key `dmCannotUseCommands` will be ignored");
+        assert_eq!(issue.suggestion.as_deref(), Some("did you mean `dmCannotUseCommand`?"));
+        assert_eq!(issue_at(&report.issues, "NightlyFeatures.thred").suggestion.as_deref(), Some("did you mean `threads`?"));
+    }
+
+    #[test]
+    fn truncates_the_discord_message() {
+        let unknown_keys = (0..100).map(|index| format!("unknownKey{index} = {index}")).collect::<Vec<_>>();
+        let source = unknown_keys.join("\n") + "\n" + &template::default_config_template();
+
+        let report = super::validate(&source);
+        let message = report.to_discord_message();
+
+        assert_eq!(report.warnings().count(), 100);
+        assert!(message.len() <= MAX_DISCORD_MESSAGE_LENGTH + "... and 100 more".len(), "{}", message.len());
+        assert!(message.contains(":warning: **Warning** at line 1, column 1 (`unknownKey0`)"));
+
+        let (shown, summary) = message.rsplit_once('\n').unwrap();
+        let shown = shown.matches(":warning:").count();
+
+        assert_eq!(summary, format!("... and {remaining} more", remaining = 100 - shown));
+    }
+
+    #[test]
+    fn known_keys_match_the_serialized_configuration() {
+        let template = template::default_config_template();
+        let mut config = crate::from_string(template.clone()).unwrap();
+        config.DashboardAccess.push(DashboardAccess {
+            userId: UserId(1000),
+            accessLevel: AccessLevel::Owner
+        });
+
+        let serialized = toml::from_str::<Value>(&crate::to_string(&config).unwrap()).unwrap();
+
+        assert_eq!(sorted_keys(&serialized), sorted(ROOT_KEYS));
+        assert_eq!(sorted_keys(&serialized["DashboardAccess"][0]), sorted(DASHBOARD_ACCESS_KEYS));
+        assert_eq!(sorted_keys(&serialized["GuildConfiguration"]), sorted(GUILD_CONFIGURATION_KEYS));
+        assert_eq!(sorted_keys(&serialized["NightlyFeatures"]), sorted(NIGHTLY_FEATURES_KEYS));
+
+        // the template documents every key
+        let template = toml::from_str::<Value>(&template).unwrap();
+
+        assert_eq!(sorted_keys(&template), sorted(ROOT_KEYS));
+        assert_eq!(sorted_keys(&template["GuildConfiguration"]), sorted(GUILD_CONFIGURATION_KEYS));
+        assert_eq!(sorted_keys(&template["NightlyFeatures"]), sorted(NIGHTLY_FEATURES_KEYS));
+    }
+}
@@ -8,7 +8,10 @@ use base64::DecodeError;
 
 use ctrlc::Error as CtrlcError;
 
-use toml::de::Error as TomlDeserializationError;
+use toml::{
+    de::Error as TomlDeserializationError,
+    ser::Error as TomlSerializationError
+};
 
 use crate::discord::{
     embed_builder::{
@@ -158,6 +161,18 @@ pub enum HarTexError {
         error: TomlDeserializationError
     },
 
+    /// # Enum Variant `TomlSerializationError`
+    ///
+    /// A wrapper around `toml::ser::Error`
+    ///
+    /// ## Fields
+    ///
+    /// - `error`, type `Error`: the TOML serialization error returned when attempting to
+    ///                          serialize TOML.
+    TomlSerializationError {
+        error: TomlSerializationError
+    },
+
     /// # Enum Variant `HarTexError::TwilightHttpError`
     ///
     /// A wrapper around `twilight_http::error::Error`.
@@ -328,6 +343,14 @@ impl From<TomlDeserializationError> for HarTexError {
     }
 }
 
+impl From<TomlSerializationError> for HarTexError {
+    fn from(error: TomlSerializationError) -> Self {
+        Self::TomlSerializationError {
+            error
+        }
+    }
+}
+
 impl From<UpdateGuildMemberError> for HarTexError {
     fn from(error: UpdateGuildMemberError) -> Self {
         Self::UpdateGuildMemberError {
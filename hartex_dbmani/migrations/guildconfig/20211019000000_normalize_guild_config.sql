@@ -0,0 +1,31 @@
+-- Replaces the per-guild "Guild{id}" tables with a single "GuildConfig" table keyed by the guild
+-- id, storing the TOML configuration as plain text rather than base64.
+--
+-- The existing per-guild tables are copied into the new table but are left in place, so that
+-- they can be inspected or restored should the migration need to be reverted; they are no
+-- longer read by the bot and may be dropped manually afterwards.
+
+CREATE TABLE IF NOT EXISTS public."GuildConfig" (
+    "GuildId" BIGINT PRIMARY KEY,
+    "TomlConfig" TEXT NOT NULL
+);
+
+DO $$
+DECLARE
+    guild_table RECORD;
+BEGIN
+    FOR guild_table IN
+        SELECT table_name
+        FROM information_schema.tables
+        WHERE table_schema = 'public' AND table_name ~ '^Guild[0-9]+$'
+    LOOP
+        EXECUTE format(
+            'INSERT INTO public."GuildConfig" ("GuildId", "TomlConfig") '
+                || 'SELECT %s, convert_from(decode("TomlConfig", ''base64''), ''UTF8'') FROM public.%I LIMIT 1 '
+                || 'ON CONFLICT ("GuildId") DO NOTHING',
+            substring(guild_table.table_name FROM 6),
+            guild_table.table_name
+        );
+    END LOOP;
+END
+$$;
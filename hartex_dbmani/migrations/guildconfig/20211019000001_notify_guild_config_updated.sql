@@ -0,0 +1,16 @@
+-- Notifies the "guild_config_updated" channel with the guild id as the payload whenever the
+-- configuration of a guild is inserted or updated, so that running bots can reload it.
+
+CREATE OR REPLACE FUNCTION public."NotifyGuildConfigUpdated"() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('guild_config_updated', NEW."GuildId"::TEXT);
+    RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS "GuildConfigUpdated" ON public."GuildConfig";
+
+CREATE TRIGGER "GuildConfigUpdated"
+    AFTER INSERT OR UPDATE ON public."GuildConfig"
+    FOR EACH ROW
+    EXECUTE PROCEDURE public."NotifyGuildConfigUpdated"();
@@ -0,0 +1,9 @@
+-- Creates the "Whitelist" table, which holds the guilds that are allowed to use the bot.
+--
+-- "GuildName": the name of the guild at the time it was whitelisted
+-- "GuildId": the id of the guild
+
+CREATE TABLE IF NOT EXISTS public."Whitelist" (
+    "GuildName" TEXT NOT NULL,
+    "GuildId" BIGINT PRIMARY KEY
+);
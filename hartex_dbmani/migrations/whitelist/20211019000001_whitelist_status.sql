@@ -0,0 +1,16 @@
+-- Adds the status of the whitelist, as well as auditing information, to the "Whitelist" table.
+--
+-- "Status": either 'Active' or 'Deactivated'; guilds whose whitelist is deactivated are treated as
+--           not whitelisted
+-- "Reason": the reason the whitelist was deactivated; NULL when the whitelist is active
+-- "AddedBy": the user id of the user who whitelisted the guild; NULL for guilds whitelisted before
+--            this migration
+-- "CreatedAt": when the guild was whitelisted
+-- "UpdatedAt": when the whitelist was last modified
+
+ALTER TABLE public."Whitelist"
+    ADD COLUMN IF NOT EXISTS "Status" TEXT NOT NULL DEFAULT 'Active' CHECK ("Status" IN ('Active', 'Deactivated')),
+    ADD COLUMN IF NOT EXISTS "Reason" TEXT,
+    ADD COLUMN IF NOT EXISTS "AddedBy" BIGINT,
+    ADD COLUMN IF NOT EXISTS "CreatedAt" TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ADD COLUMN IF NOT EXISTS "UpdatedAt" TIMESTAMPTZ NOT NULL DEFAULT now();
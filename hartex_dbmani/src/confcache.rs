@@ -0,0 +1,208 @@
+//! # The `confcache` Module
+//!
+//! This module implements a cache of the parsed guild configurations in front of the repository,
+//! so that frequently accessed configuration (for example, by per-message features) does not need
+//! to be retrieved from the database and deserialized on every access.
+
+use std::{
+    sync::{
+        atomic::{
+            AtomicU64,
+            Ordering
+        },
+        Arc
+    },
+    time::{
+        Duration,
+        Instant
+    }
+};
+
+use dashmap::DashMap;
+
+use hartex_conftoml::TomlConfig;
+
+use hartex_core::{
+    discord::model::id::GuildId,
+    error::HarTexResult
+};
+
+use hartex_logging::{
+    error,
+    verbose
+};
+
+use crate::repository::SharedRepository;
+
+/// # Constant `DEFAULT_TTL`
+///
+/// The default time-to-live of a cached guild configuration.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// # Struct `GuildConfigCache`
+///
+/// A cache of parsed guild configurations with a time-to-live. Cloning this structure is cheap,
+/// and all clones share the same cache.
+#[derive(Clone)]
+pub struct GuildConfigCache {
+    inner: Arc<GuildConfigCacheInner>
+}
+
+impl GuildConfigCache {
+    /// # Constructor `GuildConfigCache::new`
+    ///
+    /// Creates a new, empty `GuildConfigCache`.
+    ///
+    /// ## Parameters
+    /// - `repository`, type `SharedRepository`: the repository to retrieve configurations from on
+    ///                                          cache misses
+    /// - `ttl`, type `Duration`: the time-to-live of the cached configurations
+    pub fn new(repository: SharedRepository, ttl: Duration) -> Self {
+        Self {
+            inner: Arc::new(GuildConfigCacheInner {
+                repository,
+                ttl,
+                entries: DashMap::new(),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0)
+            })
+        }
+    }
+
+    /// # Asynchronous Instance Method `GuildConfigCache::get`
+    ///
+    /// Returns the configuration of a guild, retrieving it from the repository if it is not cached
+    /// or the cached configuration has expired.
+    ///
+    /// ## Parameters
+    /// - `guild_id`, type `GuildId`: the guild id to get the configuration for
+    pub async fn get(&self, guild_id: GuildId) -> HarTexResult<Arc<TomlConfig>> {
+        if let Some(entry) = self.inner.entries.get(&guild_id) {
+            if entry.inserted_at.elapsed() < self.inner.ttl {
+                self.inner.hits.fetch_add(1, Ordering::Relaxed);
+
+                return Ok(entry.config.clone());
+            }
+        }
+
+        self.inner.misses.fetch_add(1, Ordering::Relaxed);
+
+        let config = Arc::new(self.inner.repository.guild_config(guild_id).await?.config);
+        self.inner.entries.insert(guild_id, CachedConfig {
+            config: config.clone(),
+            inserted_at: Instant::now()
+        });
+
+        Ok(config)
+    }
+
+    /// # Asynchronous Instance Method `GuildConfigCache::reload`
+    ///
+    /// Retrieves the configuration of a guild from the repository regardless of whether it is
+    /// cached, and replaces the cached configuration with it. If the configuration cannot be
+    /// retrieved or is invalid, the previously cached configuration, if any, is kept.
+    ///
+    /// ## Parameters
+    /// - `guild_id`, type `GuildId`: the guild id to reload the configuration for
+    pub async fn reload(&self, guild_id: GuildId) -> HarTexResult<Arc<TomlConfig>> {
+        let config = match self.inner.repository.guild_config(guild_id).await {
+            Ok(migrated) => Arc::new(migrated.config),
+            Err(error) => {
+                error!(guild_id = guild_id; "failed to reload configuration of the guild; keeping the previous configuration");
+
+                return Err(error);
+            }
+        };
+
+        self.inner.entries.insert(guild_id, CachedConfig {
+            config: config.clone(),
+            inserted_at: Instant::now()
+        });
+
+        Ok(config)
+    }
+
+    /// # Instance Method `GuildConfigCache::invalidate`
+    ///
+    /// Removes the cached configuration of a guild, so that it is retrieved from the repository
+    /// on the next access.
+    ///
+    /// ## Parameters
+    /// - `guild_id`, type `GuildId`: the guild id to invalidate the configuration for
+    pub fn invalidate(&self, guild_id: GuildId) {
+        verbose!(guild_id = guild_id; "invalidating cached configuration of the guild");
+
+        self.inner.entries.remove(&guild_id);
+    }
+
+    /// # Instance Method `GuildConfigCache::invalidate_all`
+    ///
+    /// Removes all the cached configurations.
+    pub fn invalidate_all(&self) {
+        self.inner.entries.clear();
+    }
+
+    /// # Instance Method `GuildConfigCache::metrics`
+    ///
+    /// Returns a snapshot of the metrics of the cache.
+    pub fn metrics(&self) -> GuildConfigCacheMetrics {
+        GuildConfigCacheMetrics {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            size: self.inner.entries.len()
+        }
+    }
+
+    /// # Instance Method `GuildConfigCache::repository`
+    ///
+    /// Returns the repository that the cache retrieves configurations from.
+    pub fn repository(&self) -> &SharedRepository {
+        &self.inner.repository
+    }
+}
+
+/// # Struct `GuildConfigCacheMetrics`
+///
+/// A snapshot of the metrics of a `GuildConfigCache`.
+#[derive(Clone, Copy, Debug)]
+pub struct GuildConfigCacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    pub size: usize
+}
+
+impl GuildConfigCacheMetrics {
+    /// # Instance Method `GuildConfigCacheMetrics::hit_rate`
+    ///
+    /// Returns the ratio of cache hits to all accesses, between `0.0` and `1.0`; or `0.0` if the
+    /// cache has never been accessed.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+
+        if total == 0 {
+            0.0
+        }
+        else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// # Struct `GuildConfigCacheInner`
+///
+/// The inner structure for `GuildConfigCache`.
+struct GuildConfigCacheInner {
+    repository: SharedRepository,
+    ttl: Duration,
+    entries: DashMap<GuildId, CachedConfig>,
+    hits: AtomicU64,
+    misses: AtomicU64
+}
+
+/// # Struct `CachedConfig`
+///
+/// A cached guild configuration.
+struct CachedConfig {
+    config: Arc<TomlConfig>,
+    inserted_at: Instant
+}
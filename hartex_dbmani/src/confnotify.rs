@@ -0,0 +1,202 @@
+//! # The `confnotify` Module
+//!
+//! This module implements a background task that listens for notifications of guild configuration
+//! changes from the guild configuration database, using PostgreSQL `LISTEN`/`NOTIFY`; reloads
+//! the changed configurations into the configuration cache, and reports the reloaded guilds so
+//! that the bot can re-apply any side effects of the configuration.
+//!
+//! The notifications are sent by a trigger on the `GuildConfig` table, created by the migrations
+//! in the `migrations` module. The listener holds a dedicated connection, outside of the connection
+//! pools, so that the pools can be closed independently of it.
+
+use std::{
+    pin::Pin,
+    task::{
+        Context,
+        Poll
+    },
+    time::Duration
+};
+
+use futures_channel::{
+    mpsc::{
+        self,
+        UnboundedReceiver,
+        UnboundedSender
+    },
+    oneshot
+};
+
+use futures_util::{
+    Stream,
+    StreamExt
+};
+
+use sqlx::postgres::PgListener;
+
+use tokio::task::JoinHandle;
+
+use hartex_core::discord::model::id::GuildId;
+
+use hartex_logging::{
+    error,
+    verbose,
+    warn
+};
+
+use crate::confcache::GuildConfigCache;
+
+/// # Constant `CHANNEL`
+///
+/// The notification channel that guild configuration changes are sent to.
+pub const CHANNEL: &str = "guild_config_updated";
+
+/// # Struct `ConfigurationReloads`
+///
+/// A stream of the ids of the guilds whose configuration has been reloaded into the configuration
+/// cache after it was changed in the database.
+pub struct ConfigurationReloads {
+    receiver: UnboundedReceiver<GuildId>
+}
+
+impl Stream for ConfigurationReloads {
+    type Item = GuildId;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(context)
+    }
+}
+
+/// # Struct `ConfigurationListener`
+///
+/// A handle to the background task listening for guild configuration changes.
+pub struct ConfigurationListener {
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<()>
+}
+
+impl ConfigurationListener {
+    /// # Asynchronous Instance Method `ConfigurationListener::stop`
+    ///
+    /// Stops the background task, and waits for it to close its connection. The stream of reloaded
+    /// guilds ends once the task has stopped.
+    pub async fn stop(self) {
+        // the task may have stopped already, in which case there is nothing to signal
+        let _ = self.shutdown.send(());
+
+        if let Err(error) = self.task.await {
+            warn!("the guild configuration change listener did not stop cleanly: {error:?}");
+        }
+    }
+}
+
+/// # Function `listen`
+///
+/// Spawns the background task listening for guild configuration changes, and returns a stream of
+/// the guilds whose configuration has been reloaded along with a handle to stop the task. The task
+/// also stops when the handle is dropped.
+///
+/// ## Parameters
+/// - `credentials`, type `String`: the credentials (connection URL) of the guild configuration
+///                                 database
+/// - `config_cache`, type `GuildConfigCache`: the configuration cache to reload configurations into
+pub fn listen(credentials: String, config_cache: GuildConfigCache) -> (ConfigurationReloads, ConfigurationListener) {
+    let (sender, receiver) = mpsc::unbounded();
+    let (shutdown_sender, shutdown_receiver) = oneshot::channel();
+
+    let task = tokio::spawn(listen_task(credentials, config_cache, sender, shutdown_receiver));
+
+    (
+        ConfigurationReloads {
+            receiver
+        },
+        ConfigurationListener {
+            shutdown: shutdown_sender,
+            task
+        }
+    )
+}
+
+/// # Asynchronous Function `listen_task`
+///
+/// The background task listening for guild configuration changes.
+///
+/// ## Parameters
+/// - `credentials`, type `String`: the credentials (connection URL) of the guild configuration
+///                                 database
+/// - `config_cache`, type `GuildConfigCache`: the configuration cache to reload configurations into
+/// - `sender`, type `UnboundedSender<GuildId>`: the sender to report reloaded guilds to
+/// - `shutdown`, type `oneshot::Receiver<()>`: the receiver that resolves when the task is to stop
+async fn listen_task(credentials: String, config_cache: GuildConfigCache, sender: UnboundedSender<GuildId>, mut shutdown: oneshot::Receiver<()>) {
+    let mut listener = loop {
+        let error = tokio::select! {
+            _ = &mut shutdown => return,
+            result = connect_listener(&credentials) => match result {
+                Ok(listener) => break listener,
+                Err(error) => error
+            }
+        };
+
+        error!("failed to listen for guild configuration changes, retrying in 10 seconds; error: `{error:?}`");
+
+        tokio::select! {
+            _ = &mut shutdown => return,
+            _ = tokio::time::sleep(Duration::from_secs(10)) => ()
+        }
+    };
+
+    verbose!("listening for guild configuration changes on channel `{CHANNEL}`");
+
+    loop {
+        // the listener reconnects and listens to the channel again by itself if the connection
+        // is lost; notifications sent in the meantime are lost
+        let received = tokio::select! {
+            _ = &mut shutdown => break,
+            received = listener.recv() => received
+        };
+
+        let notification = match received {
+            Ok(notification) => notification,
+            Err(error) => {
+                error!("failed to receive guild configuration change notification; error: `{error:?}`");
+
+                tokio::time::sleep(Duration::from_secs(1)).await;
+
+                continue;
+            }
+        };
+
+        let guild_id = match notification.payload().parse::<u64>() {
+            Ok(id) if id != 0 => GuildId(id),
+            _ => {
+                warn!("received invalid guild configuration change notification `{payload}`", payload = notification.payload());
+
+                continue;
+            }
+        };
+
+        verbose!(guild_id = guild_id; "configuration of the guild changed; reloading");
+
+        // an invalid configuration is logged and not swapped in by the cache
+        if config_cache.reload(guild_id).await.is_ok() && sender.unbounded_send(guild_id).is_err() {
+            break;
+        }
+    }
+
+    verbose!("stopped listening for guild configuration changes");
+}
+
+/// # Asynchronous Function `connect_listener`
+///
+/// Connects a listener to the guild configuration database and listens to the notification
+/// channel.
+///
+/// ## Parameters
+/// - `credentials`, type `&str`: the credentials (connection URL) of the guild configuration
+///                               database
+async fn connect_listener(credentials: &str) -> sqlx::Result<PgListener> {
+    let mut listener = PgListener::connect(credentials).await?;
+    listener.listen(CHANNEL).await?;
+
+    Ok(listener)
+}
@@ -0,0 +1,93 @@
+//! # The `coordination` Module
+//!
+//! This module coordinates the processes of a deployment whose shards are split across several
+//! processes, so that the tasks that concern the whole bot rather than some shards, such as
+//! registering the global commands, are performed by exactly one of them: the leader.
+//!
+//! The leader is the process holding a PostgreSQL session-level advisory lock in the guild
+//! configuration database. The lock is held on a dedicated connection, outside of the connection
+//! pools, so that it is released by the database as soon as the leader exits, even if it crashes.
+
+use sqlx::{
+    postgres::PgConnection,
+    Connection
+};
+
+use hartex_core::error::{
+    HarTexError,
+    HarTexResult
+};
+
+use hartex_logging::{
+    error,
+    warn
+};
+
+/// # Constant `LEADER_LOCK_KEY`
+///
+/// The key of the advisory lock held by the leader.
+const LEADER_LOCK_KEY: i64 = 0x4861_7254_6578; // "HarTex"
+
+/// # Struct `Leadership`
+///
+/// The leadership of the current process, held until it is released or dropped.
+pub struct Leadership {
+    connection: PgConnection
+}
+
+impl Leadership {
+    /// # Static Asynchronous Method `Leadership::try_acquire`
+    ///
+    /// Tries to become the leader; returns `None` if another process is the leader.
+    ///
+    /// ## Parameters
+    /// - `credentials`, type `&str`: the credentials (connection URL) of the guild configuration
+    ///                               database
+    pub async fn try_acquire(credentials: &str) -> HarTexResult<Option<Self>> {
+        let mut connection = match PgConnection::connect(credentials).await {
+            Ok(connection) => connection,
+            Err(error) => {
+                let message = format!("failed to connect to guild configuration database for coordination; error: `{error:?}`");
+
+                error!("{message}");
+
+                return Err(HarTexError::Custom {
+                    message
+                });
+            }
+        };
+
+        let acquired = sqlx::query_scalar::<_, bool>("SELECT pg_try_advisory_lock($1); --")
+            .bind(LEADER_LOCK_KEY)
+            .fetch_one(&mut connection)
+            .await
+            .map_err(|error| HarTexError::Custom {
+                message: format!("failed to acquire leader lock; error: `{error:?}`")
+            })?;
+
+        if !acquired {
+            // closing the connection is best effort; it is dropped either way
+            let _ = connection.close().await;
+
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            connection
+        }))
+    }
+
+    /// # Asynchronous Instance Method `Leadership::release`
+    ///
+    /// Releases the leadership, so that another process can become the leader.
+    pub async fn release(mut self) {
+        if let Err(error) = sqlx::query("SELECT pg_advisory_unlock($1); --")
+            .bind(LEADER_LOCK_KEY)
+            .execute(&mut self.connection)
+            .await {
+            warn!("failed to release leader lock; it is released when the connection closes: {error:?}");
+        }
+
+        let _ = self.connection.close().await;
+    }
+}
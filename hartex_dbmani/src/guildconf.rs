@@ -1,10 +1,18 @@
 //! # The `guildconf` Module
 //!
-//! This module defines a database manipulation procedure to retrieve the TOML configuration of a
-//! speciifc guild, and deserializing it into Rust structs so that it is usable in Rust code.
+//! This module defines database manipulation procedures to retrieve the TOML configuration of a
+//! speciifc guild, and deserializing it into Rust structs so that it is usable in Rust code; as
+//! well as to update the TOML configuration of a specific guild.
+//!
+//! The configurations of all guilds are stored as plain text in the `GuildConfig` table, keyed by
+//! the guild id; the table is created, and the legacy per-guild tables are copied into it, by the
+//! migrations in the `migrations` module.
+//!
+//! Configurations written for older versions of the configuration format are upgraded when they
+//! are retrieved, but are never written back by a retrieval; the upgraded configuration is only
+//! stored when it is explicitly updated, such as with `/config upload`.
 
 use std::{
-    env,
     future::Future,
     pin::Pin,
     task::{
@@ -18,7 +26,7 @@ use sqlx::{
     Row
 };
 
-use hartex_conftoml::TomlConfig;
+use hartex_conftoml::MigratedConfig;
 
 use hartex_core::{
     discord::model::id::GuildId,
@@ -28,17 +36,23 @@ use hartex_core::{
     }
 };
 
-use hartex_logging::Logger;
+use hartex_logging::{
+    error,
+    info,
+    verbose
+};
 
 use crate::PendingFuture;
 
 /// # Struct `GetGuildConfig`
 ///
-/// Gets the guild configuration from the database.
+/// Gets the guild configuration from the database, upgraded to the current version of the
+/// configuration format, along with the migrations that have been applied to upgrade it.
 pub struct GetGuildConfig {
-    pending: Option<PendingFuture<TomlConfig>>,
+    pending: Option<PendingFuture<MigratedConfig>>,
 
-    guild_id: GuildId
+    guild_id: GuildId,
+    pool: PgPool
 }
 
 impl GetGuildConfig {
@@ -48,11 +62,13 @@ impl GetGuildConfig {
     ///
     /// ## Parameters
     /// - `guild_id`, type `GuildId`: the guild id to get the configuration for.
-    pub fn new(guild_id: GuildId) -> Self {
+    /// - `pool`, type `PgPool`: the connection pool of the guild configuration database.
+    pub fn new(guild_id: GuildId, pool: PgPool) -> Self {
         Self {
             pending: None,
 
-            guild_id
+            guild_id,
+            pool
         }
     }
 
@@ -60,22 +76,16 @@ impl GetGuildConfig {
     ///
     /// Starts the future.
     fn start(&mut self) -> HarTexResult<()> {
-        Logger::verbose(
-            "executing future `GetGuildConfig`",
-            Some(module_path!()),
-            file!(),
-            line!(),
-            column!()
-        );
+        verbose!("executing future `GetGuildConfig`");
 
-        self.pending.replace(Box::pin(exec_future(self.guild_id)));
+        self.pending.replace(Box::pin(exec_get_guild_config(self.guild_id, self.pool.clone())));
 
         Ok(())
     }
 }
 
 impl Future for GetGuildConfig {
-    type Output = HarTexResult<TomlConfig>;
+    type Output = HarTexResult<MigratedConfig>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         loop {
@@ -92,125 +102,204 @@ impl Future for GetGuildConfig {
 
 unsafe impl Send for GetGuildConfig { }
 
-/// # Asynchronous Function `exec_future`
+/// # Struct `GetGuildConfigSource`
 ///
-/// Executes the future.
-async fn exec_future(guild_id: GuildId) -> HarTexResult<TomlConfig> {
-    let db_credentials = match env::var("PGSQL_CREDENTIALS_GUILDCONFIG") {
-        Ok(credentials) => credentials,
-        Err(error) => {
-            let message = format!("failed to get database credentials; error: {error}");
+/// Gets the guild configuration from the database as it is stored, without deserializing it;
+/// returns `None` if the guild does not have a configuration.
+pub struct GetGuildConfigSource {
+    pending: Option<PendingFuture<Option<String>>>,
 
-            Logger::error(
-                &message,
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+    guild_id: GuildId,
+    pool: PgPool
+}
 
-            return Err(HarTexError::Custom {
-                message
-            });
+impl GetGuildConfigSource {
+    /// # Constructor `GetGuildConfigSource::new`
+    ///
+    /// Creates a new `GetGuildConfigSource` with the provided `guild_id`.
+    ///
+    /// ## Parameters
+    /// - `guild_id`, type `GuildId`: the guild id to get the configuration for.
+    /// - `pool`, type `PgPool`: the connection pool of the guild configuration database.
+    pub fn new(guild_id: GuildId, pool: PgPool) -> Self {
+        Self {
+            pending: None,
+
+            guild_id,
+            pool
         }
-    };
-
-    Logger::verbose(
-        "connecting to database...",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
-    );
-
-    let connection = match PgPool::connect(&db_credentials).await {
-        Ok(pool) => pool,
-        Err(error) => {
-            let message = format!("failed to connect to postgres database pool; error: `{error:?}`");
+    }
+
+    /// # Private Function `GetGuildConfigSource::start`
+    ///
+    /// Starts the future.
+    fn start(&mut self) -> HarTexResult<()> {
+        verbose!("executing future `GetGuildConfigSource`");
+
+        let guild_id = self.guild_id;
+        let pool = self.pool.clone();
+
+        self.pending.replace(Box::pin(async move {
+            exec_get_guild_config_source(guild_id, &pool).await
+        }));
+
+        Ok(())
+    }
+}
+
+impl Future for GetGuildConfigSource {
+    type Output = HarTexResult<Option<String>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            if let Some(pending) = self.pending.as_mut() {
+                return pending.as_mut().poll(cx);
+            }
+
+            if let Err(error) = self.start() {
+                return Poll::Ready(Err(error))
+            }
+        }
+    }
+}
+
+unsafe impl Send for GetGuildConfigSource { }
+
+/// # Struct `UpdateGuildConfig`
+///
+/// Inserts or replaces the guild configuration in the database.
+pub struct UpdateGuildConfig {
+    pending: Option<PendingFuture<()>>,
+
+    guild_id: GuildId,
+    config: Option<String>,
+    pool: PgPool
+}
+
+impl UpdateGuildConfig {
+    /// # Constructor `UpdateGuildConfig::new`
+    ///
+    /// Creates a new `UpdateGuildConfig` with the provided `guild_id` and `config`.
+    ///
+    /// ## Parameters
+    /// - `guild_id`, type `GuildId`: the guild id to update the configuration for.
+    /// - `config`, type `String`: the new TOML configuration of the guild.
+    /// - `pool`, type `PgPool`: the connection pool of the guild configuration database.
+    pub fn new(guild_id: GuildId, config: String, pool: PgPool) -> Self {
+        Self {
+            pending: None,
+
+            guild_id,
+            config: Some(config),
+            pool
+        }
+    }
+
+    /// # Private Function `UpdateGuildConfig::start`
+    ///
+    /// Starts the future.
+    fn start(&mut self) -> HarTexResult<()> {
+        verbose!("executing future `UpdateGuildConfig`");
+
+        let config = match self.config.take() {
+            Some(config) => config,
+            None => return Err(HarTexError::Custom {
+                message: String::from("`UpdateGuildConfig` polled after completion")
+            })
+        };
+
+        self.pending.replace(Box::pin(exec_update_guild_config(self.guild_id, config, self.pool.clone())));
+
+        Ok(())
+    }
+}
+
+impl Future for UpdateGuildConfig {
+    type Output = HarTexResult<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            if let Some(pending) = self.pending.as_mut() {
+                return pending.as_mut().poll(cx);
+            }
+
+            if let Err(error) = self.start() {
+                return Poll::Ready(Err(error))
+            }
+        }
+    }
+}
 
-            Logger::error(
-                &message,
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+unsafe impl Send for UpdateGuildConfig { }
 
-            return Err(HarTexError::Custom {
+/// # Asynchronous Function `exec_get_guild_config`
+///
+/// Executes the `GetGuildConfig` future.
+async fn exec_get_guild_config(guild_id: GuildId, connection: PgPool) -> HarTexResult<MigratedConfig> {
+    match exec_get_guild_config_source(guild_id, &connection).await? {
+        Some(config) => {
+            verbose!("deserializing toml config...");
+
+            let migrated = hartex_conftoml::from_string_migrated(config)?;
+
+            if !migrated.report.is_empty() {
+                info!(guild_id = guild_id; "the stored configuration of the guild is of an older version; it is upgraded in memory only until it is uploaded again");
+            }
+
+            Ok(migrated)
+        },
+        None => {
+            let message = format!("no configuration found for guild {guild_id}");
+
+            error!("{message}");
+
+            Err(HarTexError::Custom {
                 message
-            });
+            })
         }
-    };
+    }
+}
 
-    Logger::verbose(
-        "executing query...",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
-    );
+/// # Asynchronous Function `exec_get_guild_config_source`
+///
+/// Executes the `GetGuildConfigSource` future.
+async fn exec_get_guild_config_source(guild_id: GuildId, connection: &PgPool) -> HarTexResult<Option<String>> {
+    verbose!("executing query...");
 
-    match sqlx::query(&format!("SELECT * FROM \"Guild{guild_id}\"; --")).fetch_one(&connection)
+    match sqlx::query(r#"SELECT "TomlConfig" FROM public."GuildConfig" WHERE "GuildId" = $1; --"#)
+        .bind(guild_id.0 as i64)
+        .fetch_optional(connection)
         .await {
-        Ok(row) => {
-            let config = row.get::<String, &str>("TomlConfig");
-
-            let decoded = match base64::decode(config) {
-                Ok(bytes) => bytes,
-                Err(error) => {
-                    let message = format!("failed to decode base64; error: `{error:?}`");
-
-                    Logger::error(
-                        &message,
-                        Some(module_path!()),
-                        file!(),
-                        line!(),
-                        column!()
-                    );
-
-                    return Err(HarTexError::Base64DecodeError {
-                        error
-                    });
-                }
-            };
-
-            Logger::verbose(
-                "deserializing toml config...",
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
-
-            hartex_conftoml::from_string(match String::from_utf8(decoded) {
-                Ok(string) => string,
-                Err(error) => {
-                    let message = format!("failed to construct utf-8 string; error: `{error:?}`");
-
-                    Logger::error(
-                        &message,
-                        Some(module_path!()),
-                        file!(),
-                        line!(),
-                        column!()
-                    );
-
-                    return Err(HarTexError::Utf8ValidationError {
-                        error
-                    });
-                }
+        Ok(row) => Ok(row.map(|row| row.get::<String, &str>("TomlConfig"))),
+        Err(error) => {
+            let message = format!("failed to execute sql query; error `{error:?}`");
+
+            error!("{message}");
+
+            Err(HarTexError::Custom {
+                message
             })
-        },
+        }
+    }
+}
+
+/// # Asynchronous Function `exec_update_guild_config`
+///
+/// Executes the `UpdateGuildConfig` future.
+async fn exec_update_guild_config(guild_id: GuildId, config: String, connection: PgPool) -> HarTexResult<()> {
+    match sqlx::query(
+        r#"INSERT INTO public."GuildConfig" ("GuildId", "TomlConfig") VALUES ($1, $2)
+        ON CONFLICT ("GuildId") DO UPDATE SET "TomlConfig" = EXCLUDED."TomlConfig"; --"#
+    )
+        .bind(guild_id.0 as i64)
+        .bind(config)
+        .execute(&connection)
+        .await {
+        Ok(_) => Ok(()),
         Err(error) => {
             let message = format!("failed to execute sql query; error `{error:?}`");
 
-            Logger::error(
-                &message,
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+            error!("{message}");
 
             Err(HarTexError::Custom {
                 message
@@ -14,7 +14,13 @@ use std::{
 
 use hartex_core::error::HarTexResult;
 
+pub mod confcache;
+pub mod confnotify;
+pub mod coordination;
 pub mod guildconf;
+pub mod migrations;
+pub mod pool;
+pub mod repository;
 pub mod whitelist;
 
 /// # Typealias `PendingFuture`
@@ -0,0 +1,108 @@
+//! # The `migrations` Module
+//!
+//! This module embeds the versioned migrations of the PostgreSQL databases used by the bot into
+//! the binary, and provides a procedure to apply them.
+//!
+//! The migrations are located in the `migrations` directory of this crate, with one subdirectory
+//! per database: `guildconfig` for the guild configuration database, and `whitelist` for the
+//! whitelist database. The versions of the applied migrations are recorded by `sqlx` in the
+//! `_sqlx_migrations` table of each database.
+
+use sqlx::{
+    migrate::{
+        MigrateError,
+        Migrator
+    },
+    postgres::PgPool,
+    Row
+};
+
+use hartex_core::error::{
+    HarTexError,
+    HarTexResult
+};
+
+use hartex_logging::{
+    error,
+    verbose,
+    warn
+};
+
+use crate::pool::PoolManager;
+
+/// # Static `GUILDCONFIG_MIGRATOR`
+///
+/// The migrations of the guild configuration database.
+pub static GUILDCONFIG_MIGRATOR: Migrator = sqlx::migrate!("migrations/guildconfig");
+
+/// # Static `WHITELIST_MIGRATOR`
+///
+/// The migrations of the whitelist database.
+pub static WHITELIST_MIGRATOR: Migrator = sqlx::migrate!("migrations/whitelist");
+
+/// # Asynchronous Function `run_migrations`
+///
+/// Applies all pending migrations to all the databases.
+///
+/// Returns an error, and applies nothing to the affected database, if it has a migration applied
+/// that is unknown to the current binary; that is, the database is ahead of the binary.
+///
+/// ## Parameters
+/// - `pools`, type `&PoolManager`: the connection pools of the databases to migrate
+pub async fn run_migrations(pools: &PoolManager) -> HarTexResult<()> {
+    run_migrator(&GUILDCONFIG_MIGRATOR, pools.guildconfig(), "guild configuration").await?;
+    run_migrator(&WHITELIST_MIGRATOR, pools.whitelist(), "whitelist").await
+}
+
+/// # Asynchronous Function `run_migrator`
+///
+/// Applies the pending migrations of a migrator to a database, and logs the versions applied to
+/// the database.
+///
+/// ## Parameters
+/// - `migrator`, type `&Migrator`: the migrator to run
+/// - `pool`, type `&PgPool`: the connection pool of the database to migrate
+/// - `database`, type `&str`: the human-readable name of the database, used in log messages
+async fn run_migrator(migrator: &Migrator, pool: &PgPool, database: &str) -> HarTexResult<()> {
+    verbose!("running migrations of {database} database...");
+
+    if let Err(error) = migrator.run(pool).await {
+        let message = match error {
+            MigrateError::VersionMissing(version) => format!(
+                "{database} database has migration {version} applied, which is unknown to this binary; refusing to start with a database that is ahead of the binary"
+            ),
+            MigrateError::VersionMismatch(version) => format!(
+                "migration {version} applied to {database} database differs from the one in this binary"
+            ),
+            MigrateError::Dirty(version) => format!(
+                "migration {version} of {database} database was partially applied; manual intervention is required"
+            ),
+            error => format!("failed to run migrations of {database} database; error: `{error:?}`")
+        };
+
+        error!("{message}");
+
+        return Err(HarTexError::Custom {
+            message
+        });
+    }
+
+    match sqlx::query(r#"SELECT "version", "description" FROM public."_sqlx_migrations" ORDER BY "version"; --"#)
+        .fetch_all(pool)
+        .await {
+        Ok(rows) => {
+            for row in rows {
+                verbose!(
+                    "{database} database has migration {version} applied: {description}",
+                    version = row.get::<i64, &str>("version"),
+                    description = row.get::<String, &str>("description")
+                );
+            }
+        }
+        Err(error) => {
+            warn!("failed to list applied migrations of {database} database; error: `{error:?}`");
+        }
+    }
+
+    Ok(())
+}
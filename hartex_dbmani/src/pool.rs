@@ -0,0 +1,177 @@
+//! # The `pool` Module
+//!
+//! This module implements a manager of the PostgreSQL connection pools, which is created once at
+//! startup and shared across all database operations.
+
+use std::time::Duration;
+
+use sqlx::postgres::{
+    PgPool,
+    PgPoolOptions
+};
+
+use hartex_core::error::{
+    HarTexError,
+    HarTexResult
+};
+
+use hartex_logging::{
+    error,
+    verbose
+};
+
+/// # Struct `PoolOptions`
+///
+/// The options used when creating the connection pools.
+#[derive(Clone, Debug)]
+pub struct PoolOptions {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub connect_timeout: Duration,
+    pub idle_timeout: Option<Duration>,
+    pub max_lifetime: Option<Duration>,
+    pub test_before_acquire: bool
+}
+
+impl PoolOptions {
+    /// # Private Instance Method `PoolOptions::to_pg_pool_options`
+    ///
+    /// Converts the current options to the options used by `sqlx`.
+    fn to_pg_pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .connect_timeout(self.connect_timeout)
+            .idle_timeout(self.idle_timeout)
+            .max_lifetime(self.max_lifetime)
+            .test_before_acquire(self.test_before_acquire)
+    }
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            min_connections: 1,
+            connect_timeout: Duration::from_secs(10),
+            idle_timeout: Some(Duration::from_secs(10 * 60)),
+            max_lifetime: Some(Duration::from_secs(30 * 60)),
+            test_before_acquire: true
+        }
+    }
+}
+
+/// # Struct `PoolManager`
+///
+/// Manages the connection pools of the PostgreSQL databases used by the bot. Cloning this
+/// structure is cheap, and all clones share the same pools.
+#[derive(Clone)]
+pub struct PoolManager {
+    guildconfig: PgPool,
+    whitelist: PgPool
+}
+
+impl PoolManager {
+    /// # Static Asynchronous Method `PoolManager::connect`
+    ///
+    /// Creates the connection pools with the given credentials, and verifies that the databases are
+    /// reachable; so that wrong credentials are reported at startup.
+    ///
+    /// ## Parameters
+    /// - `guildconfig_credentials`, type `&str`: the credentials (connection URL) of the guild
+    ///                                           configuration database
+    /// - `whitelist_credentials`, type `&str`: the credentials (connection URL) of the whitelist
+    ///                                         database
+    /// - `options`, type `PoolOptions`: the options to create the pools with
+    pub async fn connect(guildconfig_credentials: &str, whitelist_credentials: &str, options: PoolOptions) -> HarTexResult<Self> {
+        let guildconfig = connect_pool(guildconfig_credentials, "guild configuration", &options).await?;
+        let whitelist = connect_pool(whitelist_credentials, "whitelist", &options).await?;
+
+        Ok(Self {
+            guildconfig,
+            whitelist
+        })
+    }
+
+    /// # Instance Method `PoolManager::guildconfig`
+    ///
+    /// Returns the connection pool of the guild configuration database.
+    pub fn guildconfig(&self) -> &PgPool {
+        &self.guildconfig
+    }
+
+    /// # Instance Method `PoolManager::whitelist`
+    ///
+    /// Returns the connection pool of the whitelist database.
+    pub fn whitelist(&self) -> &PgPool {
+        &self.whitelist
+    }
+
+    /// # Asynchronous Instance Method `PoolManager::ping`
+    ///
+    /// Checks whether all the databases are reachable.
+    pub async fn ping(&self) -> HarTexResult<()> {
+        ping_pool(&self.guildconfig, "guild configuration").await?;
+        ping_pool(&self.whitelist, "whitelist").await
+    }
+
+    /// # Asynchronous Instance Method `PoolManager::close`
+    ///
+    /// Closes all the connection pools, waiting for the connections currently in use to be
+    /// returned.
+    pub async fn close(&self) {
+        self.guildconfig.close().await;
+        self.whitelist.close().await;
+    }
+}
+
+/// # Asynchronous Function `connect_pool`
+///
+/// Creates a connection pool to the database with the specified credentials, and checks its
+/// health.
+///
+/// ## Parameters
+/// - `credentials`, type `&str`: the credentials (connection URL) of the database
+/// - `database`, type `&str`: the human-readable name of the database, used in error messages
+/// - `options`, type `&PoolOptions`: the options to create the pool with
+async fn connect_pool(credentials: &str, database: &str, options: &PoolOptions) -> HarTexResult<PgPool> {
+    verbose!("connecting to {database} database...");
+
+    let pool = match options.to_pg_pool_options().connect(credentials).await {
+        Ok(pool) => pool,
+        Err(error) => {
+            let message = format!("failed to connect to {database} database; are the credentials correct? error: `{error:?}`");
+
+            error!("{message}");
+
+            return Err(HarTexError::Custom {
+                message
+            });
+        }
+    };
+
+    ping_pool(&pool, database).await?;
+
+    Ok(pool)
+}
+
+/// # Asynchronous Function `ping_pool`
+///
+/// Checks whether the database behind a connection pool is reachable by executing a trivial query.
+///
+/// ## Parameters
+/// - `pool`, type `&PgPool`: the pool to check
+/// - `database`, type `&str`: the human-readable name of the database, used in error messages
+async fn ping_pool(pool: &PgPool, database: &str) -> HarTexResult<()> {
+    if let Err(error) = sqlx::query("SELECT 1; --").execute(pool).await {
+        let message = format!("health check of {database} database failed; error: `{error:?}`");
+
+        error!("{message}");
+
+        return Err(HarTexError::Custom {
+            message
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,366 @@
+//! # The `inmemory` Module
+//!
+//! This module implements the `Repository` trait with an in-memory backend, for use in tests and
+//! local development where a PostgreSQL database is not available.
+
+use std::sync::Arc;
+
+use dashmap::DashMap;
+
+use hartex_conftoml::MigratedConfig;
+
+use hartex_core::{
+    discord::model::id::{
+        GuildId,
+        UserId
+    },
+    error::{
+        HarTexError,
+        HarTexResult
+    },
+    time::Utc
+};
+
+use hartex_utils::FutureRetType;
+
+use crate::{
+    repository::Repository,
+    whitelist::model::{
+        WhitelistStatus,
+        WhitelistedGuild
+    }
+};
+
+/// # Struct `InMemoryRepository`
+///
+/// A repository that keeps all its data in memory; the data is lost when the process exits.
+#[derive(Clone, Default)]
+pub struct InMemoryRepository {
+    inner: Arc<InMemoryRepositoryInner>
+}
+
+impl InMemoryRepository {
+    /// # Constructor `InMemoryRepository::new`
+    ///
+    /// Creates a new, empty `InMemoryRepository`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Instance Method `InMemoryRepository::insert_guild_config`
+    ///
+    /// Inserts or replaces the TOML configuration of a guild.
+    ///
+    /// ## Parameters
+    /// - `guild_id`, type `GuildId`: the guild id to insert the configuration for
+    /// - `config`, type `impl Into<String>`: the TOML configuration of the guild
+    pub fn insert_guild_config(&self, guild_id: GuildId, config: impl Into<String>) {
+        self.inner.guild_configs.insert(guild_id, config.into());
+    }
+
+    /// # Instance Method `InMemoryRepository::insert_whitelisted_guild`
+    ///
+    /// Inserts or replaces a whitelisted guild.
+    ///
+    /// ## Parameters
+    /// - `guild`, type `WhitelistedGuild`: the guild to whitelist
+    pub fn insert_whitelisted_guild(&self, guild: WhitelistedGuild) {
+        self.inner.whitelisted_guilds.insert(guild.GuildId, guild);
+    }
+}
+
+impl Repository for InMemoryRepository {
+    fn guild_config<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, MigratedConfig> {
+        Box::pin(get_guild_config(self.inner.clone(), guild_id))
+    }
+
+    fn guild_config_source<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, Option<String>> {
+        Box::pin(get_guild_config_source(self.inner.clone(), guild_id))
+    }
+
+    fn update_guild_config<'asynchronous_trait>(&self, guild_id: GuildId, config: String) -> FutureRetType<'asynchronous_trait, ()> {
+        Box::pin(update_guild_config(self.inner.clone(), guild_id, config))
+    }
+
+    fn whitelisted_guilds<'asynchronous_trait>(&self) -> FutureRetType<'asynchronous_trait, Vec<WhitelistedGuild>> {
+        Box::pin(get_whitelisted_guilds(self.inner.clone()))
+    }
+
+    fn add_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId, guild_name: String, added_by: UserId) -> FutureRetType<'asynchronous_trait, ()> {
+        Box::pin(add_whitelisted_guild(self.inner.clone(), guild_id, guild_name, added_by))
+    }
+
+    fn deactivate_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId, reason: String) -> FutureRetType<'asynchronous_trait, bool> {
+        Box::pin(deactivate_whitelisted_guild(self.inner.clone(), guild_id, reason))
+    }
+
+    fn remove_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, bool> {
+        Box::pin(remove_whitelisted_guild(self.inner.clone(), guild_id))
+    }
+}
+
+/// # Struct `InMemoryRepositoryInner`
+///
+/// The inner structure for `InMemoryRepository`.
+#[derive(Default)]
+struct InMemoryRepositoryInner {
+    guild_configs: DashMap<GuildId, String>,
+    whitelisted_guilds: DashMap<u64, WhitelistedGuild>
+}
+
+/// # Asynchronous Function `get_guild_config`
+///
+/// Retrieves and deserializes the configuration of a guild from the in-memory storage, upgraded to
+/// the current version; the stored configuration is left unchanged.
+///
+/// ## Parameters
+/// - `inner`, type `Arc<InMemoryRepositoryInner>`: the in-memory storage
+/// - `guild_id`, type `GuildId`: the guild id to get the configuration for
+async fn get_guild_config(inner: Arc<InMemoryRepositoryInner>, guild_id: GuildId) -> HarTexResult<MigratedConfig> {
+    let config = match inner.guild_configs.get(&guild_id) {
+        Some(config) => config.value().clone(),
+        None => return Err(HarTexError::Custom {
+            message: format!("no configuration found for guild {guild_id}")
+        })
+    };
+
+    hartex_conftoml::from_string_migrated(config)
+}
+
+/// # Asynchronous Function `get_guild_config_source`
+///
+/// Retrieves the configuration of a guild from the in-memory storage as it is stored, if any.
+///
+/// ## Parameters
+/// - `inner`, type `Arc<InMemoryRepositoryInner>`: the in-memory storage
+/// - `guild_id`, type `GuildId`: the guild id to get the configuration for
+async fn get_guild_config_source(inner: Arc<InMemoryRepositoryInner>, guild_id: GuildId) -> HarTexResult<Option<String>> {
+    Ok(inner.guild_configs.get(&guild_id).map(|config| config.value().clone()))
+}
+
+/// # Asynchronous Function `update_guild_config`
+///
+/// Inserts or replaces the configuration of a guild in the in-memory storage.
+///
+/// ## Parameters
+/// - `inner`, type `Arc<InMemoryRepositoryInner>`: the in-memory storage
+/// - `guild_id`, type `GuildId`: the guild id to update the configuration for
+/// - `config`, type `String`: the new TOML configuration of the guild
+async fn update_guild_config(inner: Arc<InMemoryRepositoryInner>, guild_id: GuildId, config: String) -> HarTexResult<()> {
+    inner.guild_configs.insert(guild_id, config);
+
+    Ok(())
+}
+
+/// # Asynchronous Function `get_whitelisted_guilds`
+///
+/// Retrieves the whitelisted guilds from the in-memory storage.
+///
+/// ## Parameters
+/// - `inner`, type `Arc<InMemoryRepositoryInner>`: the in-memory storage
+async fn get_whitelisted_guilds(inner: Arc<InMemoryRepositoryInner>) -> HarTexResult<Vec<WhitelistedGuild>> {
+    Ok(inner.whitelisted_guilds
+        .iter()
+        .map(|entry| entry.value().clone())
+        .collect())
+}
+
+/// # Asynchronous Function `add_whitelisted_guild`
+///
+/// Whitelists a guild in the in-memory storage, or reactivates its whitelist.
+///
+/// ## Parameters
+/// - `inner`, type `Arc<InMemoryRepositoryInner>`: the in-memory storage
+/// - `guild_id`, type `GuildId`: the guild to whitelist
+/// - `guild_name`, type `String`: the name of the guild
+/// - `added_by`, type `UserId`: the user who whitelisted the guild
+async fn add_whitelisted_guild(inner: Arc<InMemoryRepositoryInner>, guild_id: GuildId, guild_name: String, added_by: UserId) -> HarTexResult<()> {
+    let now = Utc::now();
+
+    inner.whitelisted_guilds
+        .entry(guild_id.0)
+        .and_modify(|guild| {
+            guild.GuildName = guild_name.clone();
+            guild.Status = WhitelistStatus::Active;
+            guild.Reason = None;
+            guild.AddedBy = Some(added_by.0);
+            guild.UpdatedAt = now;
+        })
+        .or_insert_with(|| WhitelistedGuild {
+            GuildName: guild_name,
+            GuildId: guild_id.0,
+            Status: WhitelistStatus::Active,
+            Reason: None,
+            AddedBy: Some(added_by.0),
+            CreatedAt: now,
+            UpdatedAt: now
+        });
+
+    Ok(())
+}
+
+/// # Asynchronous Function `deactivate_whitelisted_guild`
+///
+/// Deactivates the whitelist of a guild in the in-memory storage; returns `false` if the guild
+/// is not whitelisted.
+///
+/// ## Parameters
+/// - `inner`, type `Arc<InMemoryRepositoryInner>`: the in-memory storage
+/// - `guild_id`, type `GuildId`: the guild to deactivate the whitelist of
+/// - `reason`, type `String`: the reason of the deactivation
+async fn deactivate_whitelisted_guild(inner: Arc<InMemoryRepositoryInner>, guild_id: GuildId, reason: String) -> HarTexResult<bool> {
+    match inner.whitelisted_guilds.get_mut(&guild_id.0) {
+        Some(mut guild) => {
+            guild.Status = WhitelistStatus::Deactivated;
+            guild.Reason = Some(reason);
+            guild.UpdatedAt = Utc::now();
+
+            Ok(true)
+        }
+        None => Ok(false)
+    }
+}
+
+/// # Asynchronous Function `remove_whitelisted_guild`
+///
+/// Removes the whitelist of a guild from the in-memory storage; returns `false` if the guild is
+/// not whitelisted.
+///
+/// ## Parameters
+/// - `inner`, type `Arc<InMemoryRepositoryInner>`: the in-memory storage
+/// - `guild_id`, type `GuildId`: the guild to remove the whitelist of
+async fn remove_whitelisted_guild(inner: Arc<InMemoryRepositoryInner>, guild_id: GuildId) -> HarTexResult<bool> {
+    Ok(inner.whitelisted_guilds.remove(&guild_id.0).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use hartex_core::discord::model::id::{
+        GuildId,
+        UserId
+    };
+
+    use super::InMemoryRepository;
+    use crate::{
+        repository::Repository,
+        whitelist::model::WhitelistStatus
+    };
+
+    const GUILD: GuildId = GuildId(1);
+    const OTHER_GUILD: GuildId = GuildId(2);
+    const ADMINISTRATOR: UserId = UserId(3);
+
+    const VERSION_1_CONFIG: &str = "version = 1\n\n[[DashboardAccess]]\nuserId = 3\naccessLevel = 3\n\n[GuildConfiguration]\n";
+    const VERSION_2_CONFIG: &str = "version = 2\n\n[[DashboardAccess]]\nuserId = 3\naccessLevel = \"owner\"\n\n[GuildConfiguration]\n";
+
+    #[tokio::test]
+    async fn guild_config_is_upgraded_without_rewriting_the_stored_config() {
+        let repository = InMemoryRepository::new();
+        repository.insert_guild_config(GUILD, VERSION_1_CONFIG);
+
+        let migrated = repository.guild_config(GUILD).await.unwrap();
+
+        assert!(migrated.config.can_edit_config(ADMINISTRATOR));
+        assert_eq!(migrated.report.applied.len(), 1);
+        assert_eq!(repository.guild_config_source(GUILD).await.unwrap().as_deref(), Some(VERSION_1_CONFIG));
+    }
+
+    #[tokio::test]
+    async fn guild_config_of_a_guild_without_config_is_an_error() {
+        let repository = InMemoryRepository::new();
+
+        assert!(repository.guild_config(GUILD).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn guild_config_source_is_returned_as_stored() {
+        let repository = InMemoryRepository::new();
+        repository.insert_guild_config(GUILD, VERSION_2_CONFIG);
+
+        assert_eq!(repository.guild_config_source(GUILD).await.unwrap().as_deref(), Some(VERSION_2_CONFIG));
+        assert_eq!(repository.guild_config_source(OTHER_GUILD).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn update_guild_config_inserts_and_replaces_the_config() {
+        let repository = InMemoryRepository::new();
+
+        repository.update_guild_config(GUILD, String::from(VERSION_1_CONFIG)).await.unwrap();
+        assert_eq!(repository.guild_config_source(GUILD).await.unwrap().as_deref(), Some(VERSION_1_CONFIG));
+
+        repository.update_guild_config(GUILD, String::from(VERSION_2_CONFIG)).await.unwrap();
+        assert_eq!(repository.guild_config_source(GUILD).await.unwrap().as_deref(), Some(VERSION_2_CONFIG));
+    }
+
+    #[tokio::test]
+    async fn whitelisted_guilds_are_listed() {
+        let repository = InMemoryRepository::new();
+        assert!(repository.whitelisted_guilds().await.unwrap().is_empty());
+
+        repository.add_whitelisted_guild(GUILD, String::from("Guild"), ADMINISTRATOR).await.unwrap();
+        repository.add_whitelisted_guild(OTHER_GUILD, String::from("Other Guild"), ADMINISTRATOR).await.unwrap();
+
+        let mut ids = repository
+            .whitelisted_guilds()
+            .await
+            .unwrap()
+            .iter()
+            .map(|guild| guild.GuildId)
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        assert_eq!(ids, vec![GUILD.0, OTHER_GUILD.0]);
+    }
+
+    #[tokio::test]
+    async fn add_whitelisted_guild_reactivates_a_deactivated_whitelist() {
+        let repository = InMemoryRepository::new();
+        repository.add_whitelisted_guild(GUILD, String::from("Guild"), ADMINISTRATOR).await.unwrap();
+        repository.deactivate_whitelisted_guild(GUILD, String::from("reason")).await.unwrap();
+
+        repository.add_whitelisted_guild(GUILD, String::from("Renamed Guild"), ADMINISTRATOR).await.unwrap();
+
+        let guilds = repository.whitelisted_guilds().await.unwrap();
+        assert_eq!(guilds.len(), 1);
+        assert!(guilds[0].is_active());
+        assert_eq!(guilds[0].GuildName, "Renamed Guild");
+        assert_eq!(guilds[0].Reason, None);
+        assert_eq!(guilds[0].AddedBy, Some(ADMINISTRATOR.0));
+    }
+
+    #[tokio::test]
+    async fn deactivate_whitelisted_guild_records_the_reason() {
+        let repository = InMemoryRepository::new();
+        repository.add_whitelisted_guild(GUILD, String::from("Guild"), ADMINISTRATOR).await.unwrap();
+
+        assert!(repository.deactivate_whitelisted_guild(GUILD, String::from("reason")).await.unwrap());
+
+        let guilds = repository.whitelisted_guilds().await.unwrap();
+        assert_eq!(guilds[0].Status, WhitelistStatus::Deactivated);
+        assert_eq!(guilds[0].Reason.as_deref(), Some("reason"));
+    }
+
+    #[tokio::test]
+    async fn deactivate_whitelisted_guild_of_a_guild_not_whitelisted_returns_false() {
+        let repository = InMemoryRepository::new();
+
+        assert!(!repository.deactivate_whitelisted_guild(GUILD, String::from("reason")).await.unwrap());
+        assert!(repository.whitelisted_guilds().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn remove_whitelisted_guild_removes_the_whitelist() {
+        let repository = InMemoryRepository::new();
+        repository.add_whitelisted_guild(GUILD, String::from("Guild"), ADMINISTRATOR).await.unwrap();
+
+        assert!(repository.remove_whitelisted_guild(GUILD).await.unwrap());
+        assert!(repository.whitelisted_guilds().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn remove_whitelisted_guild_of_a_guild_not_whitelisted_returns_false() {
+        let repository = InMemoryRepository::new();
+
+        assert!(!repository.remove_whitelisted_guild(GUILD).await.unwrap());
+    }
+}
@@ -0,0 +1,64 @@
+//! # The `repository` Module
+//!
+//! This module defines the `Repository` trait, an abstraction over the storage of the data the bot
+//! requires, such as guild configurations and the guild whitelist; as well as its implementations.
+
+use std::sync::Arc;
+
+use hartex_conftoml::MigratedConfig;
+
+use hartex_core::discord::model::id::{
+    GuildId,
+    UserId
+};
+
+use hartex_utils::FutureRetType;
+
+use crate::whitelist::model::WhitelistedGuild;
+
+pub mod inmemory;
+pub mod postgres;
+
+/// # Trait `Repository`
+///
+/// A storage backend for the data the bot requires.
+///
+/// ## Trait Methods
+/// - `guild_config`; parameters `GuildId`; return type `FutureRetType<MigratedConfig>`: retrieves
+///                   the configuration of a guild, upgraded to the current version of the
+///                   configuration format without modifying the stored configuration
+/// - `guild_config_source`; parameters `GuildId`; return type `FutureRetType<Option<String>>`:
+///                          retrieves the configuration of a guild as it is stored, if any
+/// - `update_guild_config`; parameters `GuildId`, `String`; return type `FutureRetType<()>`:
+///                          inserts or replaces the configuration of a guild
+/// - `whitelisted_guilds`; return type `FutureRetType<Vec<WhitelistedGuild>>`: retrieves the
+///                                                                             whitelisted guilds
+/// - `add_whitelisted_guild`; parameters `GuildId`, `String`, `UserId`; return type
+///                            `FutureRetType<()>`: whitelists a guild, or reactivates its whitelist
+/// - `deactivate_whitelisted_guild`; parameters `GuildId`, `String`; return type
+///                                   `FutureRetType<bool>`: deactivates the whitelist of a guild
+///                                   with a reason; returns `false` if the guild is not whitelisted
+/// - `remove_whitelisted_guild`; parameters `GuildId`; return type `FutureRetType<bool>`: removes
+///                               the whitelist of a guild; returns `false` if the guild is not
+///                               whitelisted
+pub trait Repository {
+    fn guild_config<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, MigratedConfig>;
+
+    fn guild_config_source<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, Option<String>>;
+
+    fn update_guild_config<'asynchronous_trait>(&self, guild_id: GuildId, config: String) -> FutureRetType<'asynchronous_trait, ()>;
+
+    fn whitelisted_guilds<'asynchronous_trait>(&self) -> FutureRetType<'asynchronous_trait, Vec<WhitelistedGuild>>;
+
+    fn add_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId, guild_name: String, added_by: UserId) -> FutureRetType<'asynchronous_trait, ()>;
+
+    fn deactivate_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId, reason: String) -> FutureRetType<'asynchronous_trait, bool>;
+
+    fn remove_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, bool>;
+}
+
+/// # Typealias `SharedRepository`
+///
+/// A typealias for `Arc<dyn Repository + Send + Sync>`, the repository shared across the
+/// components of the bot.
+pub type SharedRepository = Arc<dyn Repository + Send + Sync>;
@@ -0,0 +1,107 @@
+//! # The `postgres` Module
+//!
+//! This module implements the `Repository` trait with the PostgreSQL databases as the backend.
+
+use hartex_conftoml::MigratedConfig;
+
+use hartex_core::discord::model::id::{
+    GuildId,
+    UserId
+};
+
+use hartex_metrics::database;
+
+use hartex_utils::FutureRetType;
+
+use crate::{
+    guildconf::{
+        GetGuildConfig,
+        GetGuildConfigSource,
+        UpdateGuildConfig
+    },
+    pool::PoolManager,
+    repository::Repository,
+    whitelist::{
+        model::WhitelistedGuild,
+        modify::{
+            ModifyWhitelist,
+            WhitelistOperation
+        },
+        GetWhitelistedGuilds
+    }
+};
+
+/// # Struct `PostgresRepository`
+///
+/// A repository backed by the PostgreSQL databases.
+#[derive(Clone)]
+pub struct PostgresRepository {
+    pools: PoolManager
+}
+
+impl PostgresRepository {
+    /// # Constructor `PostgresRepository::new`
+    ///
+    /// Creates a new `PostgresRepository` using the provided connection pools.
+    ///
+    /// ## Parameters
+    /// - `pools`, type `PoolManager`: the connection pools to use
+    pub fn new(pools: PoolManager) -> Self {
+        Self {
+            pools
+        }
+    }
+}
+
+impl Repository for PostgresRepository {
+    fn guild_config<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, MigratedConfig> {
+        Box::pin(database::timed("guild_config", GetGuildConfig::new(guild_id, self.pools.guildconfig().clone())))
+    }
+
+    fn guild_config_source<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, Option<String>> {
+        Box::pin(database::timed("guild_config_source", GetGuildConfigSource::new(guild_id, self.pools.guildconfig().clone())))
+    }
+
+    fn update_guild_config<'asynchronous_trait>(&self, guild_id: GuildId, config: String) -> FutureRetType<'asynchronous_trait, ()> {
+        Box::pin(database::timed("update_guild_config", UpdateGuildConfig::new(guild_id, config, self.pools.guildconfig().clone())))
+    }
+
+    fn whitelisted_guilds<'asynchronous_trait>(&self) -> FutureRetType<'asynchronous_trait, Vec<WhitelistedGuild>> {
+        Box::pin(database::timed("whitelisted_guilds", GetWhitelistedGuilds::new(self.pools.whitelist().clone())))
+    }
+
+    fn add_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId, guild_name: String, added_by: UserId) -> FutureRetType<'asynchronous_trait, ()> {
+        let modify = ModifyWhitelist::new(
+            WhitelistOperation::Add {
+                guild_id,
+                guild_name,
+                added_by
+            },
+            self.pools.whitelist().clone()
+        );
+
+        // whitelisting a guild always modifies the whitelist
+        Box::pin(async move {
+            database::timed("modify_whitelist", modify).await.map(|_| ())
+        })
+    }
+
+    fn deactivate_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId, reason: String) -> FutureRetType<'asynchronous_trait, bool> {
+        Box::pin(database::timed("modify_whitelist", ModifyWhitelist::new(
+            WhitelistOperation::Deactivate {
+                guild_id,
+                reason
+            },
+            self.pools.whitelist().clone()
+        )))
+    }
+
+    fn remove_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, bool> {
+        Box::pin(database::timed("modify_whitelist", ModifyWhitelist::new(
+            WhitelistOperation::Remove {
+                guild_id
+            },
+            self.pools.whitelist().clone()
+        )))
+    }
+}
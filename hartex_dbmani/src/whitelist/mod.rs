@@ -4,7 +4,6 @@
 //! the bot for checking whitelists.
 
 use std::{
-    env,
     future::Future,
     pin::Pin,
     task::{
@@ -23,49 +22,55 @@ use hartex_core::error::{
     HarTexResult
 };
 
-use hartex_logging::Logger;
+use hartex_logging::{
+    error,
+    verbose
+};
 
 use crate::{
     whitelist::model::WhitelistedGuild,
     PendingFuture
 };
 
-mod model;
+pub mod model;
+pub mod modify;
 
 /// # Struct `GetWhitelistedGuilds`
 ///
 /// Gets the whitelisted guilds of the bot.
 pub struct GetWhitelistedGuilds {
-    pending: Option<PendingFuture<Vec<WhitelistedGuild>>>
+    pending: Option<PendingFuture<Vec<WhitelistedGuild>>>,
+
+    pool: PgPool
 }
 
 impl GetWhitelistedGuilds {
+    /// # Constructor `GetWhitelistedGuilds::new`
+    ///
+    /// Creates a new `GetWhitelistedGuilds`.
+    ///
+    /// ## Parameters
+    /// - `pool`, type `PgPool`: the connection pool of the whitelist database.
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pending: None,
+
+            pool
+        }
+    }
+
     /// # Private Function `GetWhitelistedGuilds::start`
     ///
     /// Starts the future.
     fn start(&mut self) -> HarTexResult<()> {
-        Logger::verbose(
-            "executing future `GetWhitelistedGuilds`",
-            Some(module_path!()),
-            file!(),
-            line!(),
-            column!()
-        );
+        verbose!("executing future `GetWhitelistedGuilds`");
 
-        self.pending.replace(Box::pin(exec_future()));
+        self.pending.replace(Box::pin(exec_future(self.pool.clone())));
 
         Ok(())
     }
 }
 
-impl Default for GetWhitelistedGuilds {
-    fn default() -> Self {
-        Self {
-            pending: None
-        }
-    }
-}
-
 impl Future for GetWhitelistedGuilds {
     type Output = HarTexResult<Vec<WhitelistedGuild>>;
 
@@ -87,45 +92,7 @@ unsafe impl Send for GetWhitelistedGuilds { }
 /// # Asynchronous Function `exec_future`
 ///
 /// Executes the future.
-async fn exec_future() -> HarTexResult<Vec<WhitelistedGuild>> {
-    let db_credentials = match env::var("PGSQL_CREDENTIALS_GUILDS") {
-        Ok(credentials) => credentials,
-        Err(error) => {
-            let message = format!("failed to get database credentials; error: {error}");
-
-            Logger::error(
-                &message,
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
-
-            return Err(HarTexError::Custom {
-                message
-            });
-        }
-    };
-
-    let connection = match PgPool::connect(&db_credentials).await {
-        Ok(pool) => pool,
-        Err(error) => {
-            let message = format!("failed to connect to postgres database pool; error: `{error:?}`");
-
-            Logger::error(
-                &message,
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
-
-            return Err(HarTexError::Custom {
-                message
-            });
-        }
-    };
-
+async fn exec_future(connection: PgPool) -> HarTexResult<Vec<WhitelistedGuild>> {
     match sqlx::query_as::<Postgres, WhitelistedGuild>(r#"SELECT * FROM public."Whitelist"; --"#).fetch_all(&connection).await {
         Ok(guilds) => {
             Ok(guilds)
@@ -133,13 +100,7 @@ async fn exec_future() -> HarTexResult<Vec<WhitelistedGuild>> {
         Err(error) => {
             let message = format!("failed to execute sql query; error: `{error:?}`");
 
-            Logger::error(
-                &message,
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+            error!("{message}");
 
             Err(HarTexError::Custom {
                 message
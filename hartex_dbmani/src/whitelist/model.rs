@@ -2,25 +2,117 @@
 //!
 //! This module contains some models for use in the `GetWhitelistedGuilds` future.
 
+use std::fmt::{
+    self,
+    Display,
+    Formatter
+};
+
 use sqlx::{
+    error::BoxDynError,
     postgres::PgRow,
+    Error as SqlxError,
     Result as SqlxResult,
     Row
 };
 
+use hartex_core::time::{
+    DateTime,
+    Utc
+};
+
+/// # Struct `WhitelistedGuild`
+///
+/// Represents a whitelisted guild.
+#[derive(Clone)]
 pub struct WhitelistedGuild {
     pub GuildName: String,
-    pub GuildId: u64
+    pub GuildId: u64,
+    pub Status: WhitelistStatus,
+    pub Reason: Option<String>,
+    pub AddedBy: Option<u64>,
+    pub CreatedAt: DateTime<Utc>,
+    pub UpdatedAt: DateTime<Utc>
+}
+
+impl WhitelistedGuild {
+    /// # Instance Method `WhitelistedGuild::is_active`
+    ///
+    /// Returns whether the whitelist of the guild is active.
+    pub fn is_active(&self) -> bool {
+        self.Status == WhitelistStatus::Active
+    }
 }
 
 impl<'r> sqlx::FromRow<'r, PgRow> for WhitelistedGuild {
     fn from_row(row: &'r PgRow) -> SqlxResult<Self> {
         let name = row.try_get::<String, &str>("GuildName")?;
         let id = row.try_get::<i64, &str>("GuildId")?;
+        let status = row.try_get::<String, &str>("Status")?;
+        let reason = row.try_get::<Option<String>, &str>("Reason")?;
+        let added_by = row.try_get::<Option<i64>, &str>("AddedBy")?;
+        let created_at = row.try_get::<DateTime<Utc>, &str>("CreatedAt")?;
+        let updated_at = row.try_get::<DateTime<Utc>, &str>("UpdatedAt")?;
 
         Ok(Self {
             GuildName: name,
-            GuildId: id as u64
+            GuildId: id as u64,
+            Status: WhitelistStatus::from_db_str(&status).map_err(|error| SqlxError::ColumnDecode {
+                index: String::from("Status"),
+                source: error
+            })?,
+            Reason: reason,
+            AddedBy: added_by.map(|id| id as u64),
+            CreatedAt: created_at,
+            UpdatedAt: updated_at
         })
     }
 }
+
+/// # Enum `WhitelistStatus`
+///
+/// Represents the status of the whitelist of a guild.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WhitelistStatus {
+    /// # Enum Variant `WhitelistStatus::Active`
+    ///
+    /// The whitelist is active; the bot may be used in the guild.
+    Active,
+
+    /// # Enum Variant `WhitelistStatus::Deactivated`
+    ///
+    /// The whitelist is deactivated with a reason; the bot may not be used in the guild.
+    Deactivated
+}
+
+impl WhitelistStatus {
+    /// # Instance Method `WhitelistStatus::as_db_str`
+    ///
+    /// Returns the representation of the status stored in the database.
+    pub fn as_db_str(self) -> &'static str {
+        match self {
+            Self::Active => "Active",
+            Self::Deactivated => "Deactivated"
+        }
+    }
+
+    /// # Static Method `WhitelistStatus::from_db_str`
+    ///
+    /// Parses the representation of the status stored in the database.
+    ///
+    /// ## Parameters
+    /// - `status`, type `&str`: the status stored in the database
+    fn from_db_str(status: &str) -> Result<Self, BoxDynError> {
+        Ok(match status {
+            "Active" => Self::Active,
+            "Deactivated" => Self::Deactivated,
+            _ => return Err(format!("unknown whitelist status `{status}`").into())
+        })
+    }
+}
+
+impl Display for WhitelistStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_db_str())
+    }
+}
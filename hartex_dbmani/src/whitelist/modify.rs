@@ -0,0 +1,194 @@
+//! # The `modify` Module
+//!
+//! This module defines a database manipulation procedure for modifying the whitelist of the bot:
+//! whitelisting guilds, deactivating whitelists with a reason, and removing whitelists.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{
+        Context,
+        Poll
+    }
+};
+
+use sqlx::postgres::PgPool;
+
+use hartex_core::{
+    discord::model::id::{
+        GuildId,
+        UserId
+    },
+    error::{
+        HarTexError,
+        HarTexResult
+    }
+};
+
+use hartex_logging::{
+    error,
+    verbose
+};
+
+use crate::{
+    whitelist::model::WhitelistStatus,
+    PendingFuture
+};
+
+/// # Enum `WhitelistOperation`
+///
+/// Represents a modification to the whitelist.
+pub enum WhitelistOperation {
+    /// # Enum Variant `WhitelistOperation::Add`
+    ///
+    /// Whitelists a guild; reactivates the whitelist if the guild has previously been whitelisted.
+    ///
+    /// ## Fields
+    /// - `guild_id`, type `GuildId`: the guild to whitelist
+    /// - `guild_name`, type `String`: the name of the guild
+    /// - `added_by`, type `UserId`: the user who whitelisted the guild
+    Add {
+        guild_id: GuildId,
+        guild_name: String,
+        added_by: UserId
+    },
+
+    /// # Enum Variant `WhitelistOperation::Deactivate`
+    ///
+    /// Deactivates the whitelist of a guild with a reason.
+    ///
+    /// ## Fields
+    /// - `guild_id`, type `GuildId`: the guild to deactivate the whitelist of
+    /// - `reason`, type `String`: the reason of the deactivation
+    Deactivate {
+        guild_id: GuildId,
+        reason: String
+    },
+
+    /// # Enum Variant `WhitelistOperation::Remove`
+    ///
+    /// Removes the whitelist of a guild entirely.
+    ///
+    /// ## Fields
+    /// - `guild_id`, type `GuildId`: the guild to remove the whitelist of
+    Remove {
+        guild_id: GuildId
+    }
+}
+
+/// # Struct `ModifyWhitelist`
+///
+/// Modifies the whitelist of the bot; resolves to whether the whitelist has been modified, which
+/// is not the case if the guild to deactivate or remove the whitelist of is not whitelisted.
+pub struct ModifyWhitelist {
+    pending: Option<PendingFuture<bool>>,
+
+    operation: Option<WhitelistOperation>,
+    pool: PgPool
+}
+
+impl ModifyWhitelist {
+    /// # Constructor `ModifyWhitelist::new`
+    ///
+    /// Creates a new `ModifyWhitelist` with the provided `operation`.
+    ///
+    /// ## Parameters
+    /// - `operation`, type `WhitelistOperation`: the modification to perform.
+    /// - `pool`, type `PgPool`: the connection pool of the whitelist database.
+    pub fn new(operation: WhitelistOperation, pool: PgPool) -> Self {
+        Self {
+            pending: None,
+
+            operation: Some(operation),
+            pool
+        }
+    }
+
+    /// # Private Function `ModifyWhitelist::start`
+    ///
+    /// Starts the future.
+    fn start(&mut self) -> HarTexResult<()> {
+        verbose!("executing future `ModifyWhitelist`");
+
+        let operation = match self.operation.take() {
+            Some(operation) => operation,
+            None => return Err(HarTexError::Custom {
+                message: String::from("`ModifyWhitelist` polled after completion")
+            })
+        };
+
+        self.pending.replace(Box::pin(exec_future(operation, self.pool.clone())));
+
+        Ok(())
+    }
+}
+
+impl Future for ModifyWhitelist {
+    type Output = HarTexResult<bool>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            if let Some(pending) = self.pending.as_mut() {
+                return pending.as_mut().poll(cx);
+            }
+
+            if let Err(error) = self.start() {
+                return Poll::Ready(Err(error))
+            }
+        }
+    }
+}
+
+unsafe impl Send for ModifyWhitelist { }
+
+/// # Asynchronous Function `exec_future`
+///
+/// Executes the future.
+async fn exec_future(operation: WhitelistOperation, connection: PgPool) -> HarTexResult<bool> {
+    let result = match operation {
+        WhitelistOperation::Add { guild_id, guild_name, added_by } => {
+            sqlx::query(
+                r#"INSERT INTO public."Whitelist" ("GuildName", "GuildId", "Status", "Reason", "AddedBy")
+                VALUES ($1, $2, $3, NULL, $4)
+                ON CONFLICT ("GuildId") DO UPDATE
+                SET "GuildName" = EXCLUDED."GuildName", "Status" = EXCLUDED."Status", "Reason" = NULL,
+                    "AddedBy" = EXCLUDED."AddedBy", "UpdatedAt" = now(); --"#
+            )
+                .bind(guild_name)
+                .bind(guild_id.0 as i64)
+                .bind(WhitelistStatus::Active.as_db_str())
+                .bind(added_by.0 as i64)
+                .execute(&connection)
+                .await
+        }
+        WhitelistOperation::Deactivate { guild_id, reason } => {
+            sqlx::query(
+                r#"UPDATE public."Whitelist" SET "Status" = $1, "Reason" = $2, "UpdatedAt" = now() WHERE "GuildId" = $3; --"#
+            )
+                .bind(WhitelistStatus::Deactivated.as_db_str())
+                .bind(reason)
+                .bind(guild_id.0 as i64)
+                .execute(&connection)
+                .await
+        }
+        WhitelistOperation::Remove { guild_id } => {
+            sqlx::query(r#"DELETE FROM public."Whitelist" WHERE "GuildId" = $1; --"#)
+                .bind(guild_id.0 as i64)
+                .execute(&connection)
+                .await
+        }
+    };
+
+    match result {
+        Ok(done) => Ok(done.rows_affected() != 0),
+        Err(error) => {
+            let message = format!("failed to execute sql query; error: `{error:?}`");
+
+            error!("{message}");
+
+            Err(HarTexError::Custom {
+                message
+            })
+        }
+    }
+}
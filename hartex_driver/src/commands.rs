@@ -17,7 +17,47 @@ use hartex_core::{
     }
 };
 
-use hartex_logging::Logger;
+use hartex_logging::{
+    error,
+    info,
+    verbose
+};
+
+use hartex_metrics::http::RecordHttp;
+
+use hartex_plugins::{
+    configuration::config::Config,
+    globadmin_only::whitelist::Whitelist,
+    global::{
+        about::About,
+        ping::Ping,
+        source::Source,
+        team::Team
+    },
+    information::userinfo::Userinfo
+};
+
+/// # Function `global_commands`
+///
+/// Returns all the global slash commands of the bot.
+pub fn global_commands() -> Vec<Box<dyn Command + Send + Sync>> {
+    vec![
+        // Global Administrator Only Plugin
+        Box::new(Whitelist),
+
+        // Configuration Plugin
+        Box::new(Config),
+
+        // Global Plugin
+        Box::new(About),
+        Box::new(Ping),
+        Box::new(Source),
+        Box::new(Team),
+
+        // Information Plugin
+        Box::new(Userinfo)
+    ]
+}
 
 /// # Asynchronous Function `register_global_commands`
 ///
@@ -26,24 +66,21 @@ use hartex_logging::Logger;
 /// ## Parameters
 /// `commands`, type `Vec<Box<dyn SlashCommand + Send + Sync>>`: the commands to register.
 /// `http`, type `Client`: the Twilight HTTP client to use for registration.
-pub async fn register_global_commands(commands: Vec<Box<dyn Command + Send + Sync>>, http: Client) -> HarTexResult<()> {
+/// `dry_run`, type `bool`: whether to only log the commands that would be registered, without
+///                         registering them.
+pub async fn register_global_commands(commands: Vec<Box<dyn Command + Send + Sync>>, http: Client, dry_run: bool) -> HarTexResult<()> {
     let mut i = 1;
     let len = commands.len();
 
     let existing =  match http.get_global_commands()?
         .exec()
+        .recorded("get_global_commands")
         .await?
         .models()
         .await {
         Ok(commands) => commands,
         Err(error) => {
-            Logger::error(
-                format!("failed to obtain a list of existing global commands: {error}"),
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+            error!("failed to obtain a list of existing global commands: {error}");
 
             return Err(HarTexError::Custom {
                 message: format!("failed to obtain a list of existing global commands: {error}")
@@ -54,26 +91,22 @@ pub async fn register_global_commands(commands: Vec<Box<dyn Command + Send + Syn
     let names = existing.iter().map(|command| command.name.clone()).collect::<Vec<_>>();
 
     for command in &commands {
-        Logger::verbose(
-            format!(
-                "registering global command {i} of {len}; [name: {name}, type: {command_type:?}]",
-                name = &command.name(),
-                command_type = &command.command_type()
-            ),
-            Some(module_path!()),
-            file!(),
-            line!(),
-            column!()
+        verbose!(
+            "registering global command {i} of {len}; [name: {name}, type: {command_type:?}]",
+            name = &command.name(),
+            command_type = &command.command_type()
         );
 
         if names.contains(&command.name()) {
-            Logger::verbose(
-                "command already registered, skipping",
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+            verbose!("command already registered, skipping");
+
+            i += 1;
+
+            continue;
+        }
+
+        if dry_run {
+            info!("would register global command `{name}`", name = command.name());
 
             i += 1;
 
@@ -94,6 +127,7 @@ pub async fn register_global_commands(commands: Vec<Box<dyn Command + Send + Syn
                         .command_options(&command.optional_cmdopts())?
                         .default_permission(command.enabled_by_default())
                         .exec()
+                        .recorded("create_global_command")
                 },
                 CommandType::Message => todo!(),
                 CommandType::User => todo!()
@@ -101,13 +135,7 @@ pub async fn register_global_commands(commands: Vec<Box<dyn Command + Send + Syn
         }.await {
             Ok(_) => (),
             Err(error) => {
-                Logger::error(
-                    format!("failed to register global command {i} of {len}: {error}"),
-                    Some(module_path!()),
-                    file!(),
-                    line!(),
-                    column!()
-                );
+                error!("failed to register global command {i} of {len}: {error}");
             }
         }
 
@@ -0,0 +1,903 @@
+//! # The `config` Module
+//!
+//! This module contains the bot-level configuration of HarTex Discord bot, which is loaded and
+//! validated once at startup and passed down to the parts of the bot that need it.
+//!
+//! The configuration is layered; each layer overrides the values of the previous ones:
+//!
+//! 1. the `hartex.toml` file (or the file specified with `HARTEX_CONFIG` or `--config`), which
+//!    may be absent if the bot is configured entirely with environment variables;
+//! 2. the environment variables, including those loaded from the `.env` file;
+//! 3. the command-line flags.
+//!
+//! A large bot may split its shards across several processes, each of them running a range of
+//! the shards with `gateway.shards` (or `HARTEX_SHARDS` or `--shards`, written `FROM-TO/TOTAL`).
+//! The processes are told apart in the logs and the presence by their names.
+//!
+//! An example `hartex.toml`:
+//!
+//! ```toml
+//! token = "..."
+//! application_id = 0
+//! global_administrator_id = 0
+//! # a log level, optionally followed by the log levels of some modules
+//! log_level = "info,hartex_dbmani=verbose"
+//! # defaults to `shards FROM-TO` if a shard range is specified, and `main` otherwise
+//! process_name = "main"
+//!
+//! [logging]
+//! # where the messages are written: `stdout`, `stderr` and `file`; defaults to `stdout`, along
+//! # with `file` if a file is specified
+//! sinks = [ "stdout", "file" ]
+//! file = "hartex.log"
+//! # `text` for lines meant to be read by humans, or `json` for log aggregators
+//! format = "text"
+//!
+//! [database]
+//! backend = "postgres"
+//! guildconfig_url = "postgres://..."
+//! whitelist_url = "postgres://..."
+//!
+//! [database.pool]
+//! max_connections = 10
+//!
+//! [gateway]
+//! # derived from the commands if absent
+//! intents = [ "GUILDS", "GUILD_MEMBERS", "GUILD_PRESENCES" ]
+//! # `derived` (the default) or `all`
+//! event_types = "derived"
+//! shards = { from = 0, to = 0, total = 1 }
+//! # defaults to `hartex_sessions.{process}.json`, so that the processes of a deployment running
+//! # several of them in the same directory do not overwrite the sessions of each other
+//! sessions_file = "hartex_sessions.main.json"
+//!
+//! [cache]
+//! # `derived` (the default) or `all`
+//! resource_types = "derived"
+//!
+//! [health]
+//! # the health server is disabled if absent
+//! address = "127.0.0.1:8080"
+//!
+//! [presence]
+//! status = "online"
+//! activity_type = "watching"
+//! # `{shard}` and `{process}` are replaced with the shard id and the process name
+//! activity_name = "codebase revamp | shard {shard}"
+//! ```
+
+use std::{
+    env,
+    fs,
+    io::ErrorKind,
+    net::SocketAddr,
+    path::PathBuf,
+    str::FromStr,
+    time::Duration
+};
+
+use serde::Deserialize;
+
+use hartex_core::{
+    discord::{
+        gateway::{
+            cluster::ShardScheme,
+            Intents
+        },
+        model::{
+            gateway::presence::{
+                ActivityType,
+                Status
+            },
+            id::{
+                ApplicationId,
+                UserId
+            }
+        }
+    },
+    error::{
+        HarTexError,
+        HarTexResult
+    }
+};
+
+use hartex_dbmani::pool::PoolOptions;
+
+use hartex_logging::{
+    filter::LogFilter,
+    format::LogFormat
+};
+
+/// # Constant `DEFAULT_CONFIG_PATH`
+///
+/// The path of the bot configuration file that is loaded if no other path is specified.
+pub const DEFAULT_CONFIG_PATH: &str = "hartex.toml";
+
+/// # Constant `DEFAULT_SESSIONS_FILE`
+///
+/// The path of the file the gateway sessions are saved to on shutdown if no other path is
+/// specified; `{process}` is replaced with the name of the process, with every character other
+/// than ASCII letters, digits, `-` and `_` replaced with `_`.
+pub const DEFAULT_SESSIONS_FILE: &str = "hartex_sessions.{process}.json";
+
+/// # Constant `INTENTS`
+///
+/// The names of the gateway intents that can be specified in the configuration, along with the
+/// intents they represent.
+const INTENTS: &[(&str, Intents)] = &[
+    ("GUILDS", Intents::GUILDS),
+    ("GUILD_MEMBERS", Intents::GUILD_MEMBERS),
+    ("GUILD_BANS", Intents::GUILD_BANS),
+    ("GUILD_EMOJIS", Intents::GUILD_EMOJIS),
+    ("GUILD_INTEGRATIONS", Intents::GUILD_INTEGRATIONS),
+    ("GUILD_WEBHOOKS", Intents::GUILD_WEBHOOKS),
+    ("GUILD_INVITES", Intents::GUILD_INVITES),
+    ("GUILD_VOICE_STATES", Intents::GUILD_VOICE_STATES),
+    ("GUILD_PRESENCES", Intents::GUILD_PRESENCES),
+    ("GUILD_MESSAGES", Intents::GUILD_MESSAGES),
+    ("GUILD_MESSAGE_REACTIONS", Intents::GUILD_MESSAGE_REACTIONS),
+    ("GUILD_MESSAGE_TYPING", Intents::GUILD_MESSAGE_TYPING),
+    ("DIRECT_MESSAGES", Intents::DIRECT_MESSAGES),
+    ("DIRECT_MESSAGE_REACTIONS", Intents::DIRECT_MESSAGE_REACTIONS),
+    ("DIRECT_MESSAGE_TYPING", Intents::DIRECT_MESSAGE_TYPING)
+];
+
+/// # Struct `BotConfig`
+///
+/// The validated bot-level configuration.
+#[derive(Clone)]
+pub struct BotConfig {
+    pub token: String,
+    pub application_id: ApplicationId,
+    pub global_administrator_id: Option<UserId>,
+    pub log_filter: LogFilter,
+    pub log_sinks: Vec<LogSinkConfig>,
+    pub log_format: LogFormat,
+    /// The name of the process, telling it apart from the other processes of the deployment.
+    pub process_name: String,
+    pub database: DatabaseConfig,
+    /// The configured gateway intents; derived from the commands if `None`.
+    pub intents: Option<Intents>,
+    pub event_types: Selection,
+    pub resource_types: Selection,
+    pub shard_scheme: ShardScheme,
+    pub sessions_file: PathBuf,
+    /// The address of the health server; the health server is disabled if `None`.
+    pub health_address: Option<SocketAddr>,
+    pub presence: PresenceConfig
+}
+
+/// # Enum `Selection`
+///
+/// Which gateway event types or cache resource types to use.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Selection {
+    /// # Enum Variant `Selection::Derived`
+    ///
+    /// Only those the bot needs, as derived from the events it handles and the commands it
+    /// registers.
+    Derived,
+
+    /// # Enum Variant `Selection::All`
+    ///
+    /// All of them.
+    All
+}
+
+/// # Enum `LogSinkConfig`
+///
+/// A sink the logged messages are written to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LogSinkConfig {
+    /// # Enum Variant `LogSinkConfig::Stdout`
+    ///
+    /// The standard output.
+    Stdout,
+
+    /// # Enum Variant `LogSinkConfig::Stderr`
+    ///
+    /// The standard error.
+    Stderr,
+
+    /// # Enum Variant `LogSinkConfig::File`
+    ///
+    /// A file the messages are appended to.
+    File(PathBuf)
+}
+
+/// # Enum `DatabaseConfig`
+///
+/// The database backend to use, along with its configuration.
+#[derive(Clone)]
+pub enum DatabaseConfig {
+    /// # Enum Variant `DatabaseConfig::InMemory`
+    ///
+    /// The in-memory backend, intended for local development only; all data stored in it is lost
+    /// when the process exits.
+    InMemory,
+
+    /// # Enum Variant `DatabaseConfig::Postgres`
+    ///
+    /// The PostgreSQL backend.
+    Postgres {
+        guildconfig_url: String,
+        whitelist_url: String,
+        pool: PoolOptions
+    }
+}
+
+/// # Struct `PresenceConfig`
+///
+/// The presence of the bot, which is set for every shard once it is ready.
+#[derive(Clone)]
+pub struct PresenceConfig {
+    pub status: Status,
+    pub activity_type: ActivityType,
+    /// The name of the activity; `{shard}` is replaced with the id of the shard and `{process}`
+    /// with the name of the process.
+    pub activity_name: String
+}
+
+impl PresenceConfig {
+    /// # Instance Method `PresenceConfig::activity_name_for`
+    ///
+    /// Returns the name of the activity for the given shard.
+    ///
+    /// ## Parameters
+    /// - `shard_id`, type `u64`: the id of the shard
+    /// - `process_name`, type `&str`: the name of the process running the shard
+    pub fn activity_name_for(&self, shard_id: u64, process_name: &str) -> String {
+        self.activity_name
+            .replace("{shard}", &shard_id.to_string())
+            .replace("{process}", process_name)
+    }
+}
+
+/// # Struct `ConfigOverrides`
+///
+/// The values specified on the command line, which override those of the configuration file and
+/// the environment variables.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigOverrides {
+    pub config_path: Option<PathBuf>,
+    pub log_level: Option<String>,
+    pub log_format: Option<String>,
+    pub database_backend: Option<String>,
+    /// The range of shards to run, written `FROM-TO/TOTAL`.
+    pub shards: Option<String>,
+    pub process_name: Option<String>
+}
+
+/// # Struct `RawBotConfig`
+///
+/// The bot-level configuration as it is written, before it is validated.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawBotConfig {
+    token: Option<String>,
+    application_id: Option<u64>,
+    global_administrator_id: Option<u64>,
+    log_level: Option<String>,
+    process_name: Option<String>,
+    logging: RawLoggingConfig,
+    database: RawDatabaseConfig,
+    gateway: RawGatewayConfig,
+    cache: RawCacheConfig,
+    health: RawHealthConfig,
+    presence: RawPresenceConfig
+}
+
+/// # Struct `RawLoggingConfig`
+///
+/// The `[logging]` section of the bot-level configuration, before it is validated.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawLoggingConfig {
+    sinks: Option<Vec<String>>,
+    file: Option<String>,
+    format: Option<String>
+}
+
+/// # Struct `RawDatabaseConfig`
+///
+/// The `[database]` section of the bot-level configuration, before it is validated.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawDatabaseConfig {
+    backend: Option<String>,
+    guildconfig_url: Option<String>,
+    whitelist_url: Option<String>,
+    pool: RawPoolConfig
+}
+
+/// # Struct `RawPoolConfig`
+///
+/// The `[database.pool]` section of the bot-level configuration, before it is validated.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawPoolConfig {
+    max_connections: Option<u32>,
+    min_connections: Option<u32>,
+    connect_timeout_secs: Option<u64>,
+    idle_timeout_secs: Option<u64>,
+    max_lifetime_secs: Option<u64>,
+    test_before_acquire: Option<bool>
+}
+
+/// # Struct `RawGatewayConfig`
+///
+/// The `[gateway]` section of the bot-level configuration, before it is validated.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawGatewayConfig {
+    intents: Option<Vec<String>>,
+    event_types: Option<String>,
+    shards: Option<RawShardRange>,
+    sessions_file: Option<String>
+}
+
+/// # Struct `RawCacheConfig`
+///
+/// The `[cache]` section of the bot-level configuration, before it is validated.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawCacheConfig {
+    resource_types: Option<String>
+}
+
+/// # Struct `RawHealthConfig`
+///
+/// The `[health]` section of the bot-level configuration, before it is validated.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawHealthConfig {
+    address: Option<String>
+}
+
+/// # Struct `RawShardRange`
+///
+/// The range of shards to run, before it is validated.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawShardRange {
+    from: u64,
+    to: u64,
+    total: u64
+}
+
+impl FromStr for RawShardRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid shard range `{s}`; expected `FROM-TO/TOTAL`");
+
+        let (range, total) = s.split_once('/').ok_or_else(invalid)?;
+        let (from, to) = range.split_once('-').ok_or_else(invalid)?;
+
+        Ok(Self {
+            from: from.trim().parse().map_err(|_| invalid())?,
+            to: to.trim().parse().map_err(|_| invalid())?,
+            total: total.trim().parse().map_err(|_| invalid())?
+        })
+    }
+}
+
+/// # Struct `RawPresenceConfig`
+///
+/// The `[presence]` section of the bot-level configuration, before it is validated.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawPresenceConfig {
+    status: Option<String>,
+    activity_type: Option<String>,
+    activity_name: Option<String>
+}
+
+impl BotConfig {
+    /// # Static Method `BotConfig::load`
+    ///
+    /// Loads the bot-level configuration from the configuration file, the environment variables
+    /// and the command-line flags, in that order, and validates it. All the problems found in the
+    /// configuration are reported at once.
+    ///
+    /// The `.env` file should be loaded before calling this method.
+    ///
+    /// ## Parameters
+    /// - `overrides`, type `&ConfigOverrides`: the values specified on the command line
+    pub fn load(overrides: &ConfigOverrides) -> HarTexResult<Self> {
+        let mut raw = read_config_file(overrides)?;
+
+        apply_env_overrides(&mut raw)?;
+        apply_cli_overrides(&mut raw, overrides)?;
+
+        validate(raw)
+    }
+}
+
+/// # Function `read_config_file`
+///
+/// Reads the configuration file. A missing file is only an error if its path is specified
+/// explicitly.
+///
+/// ## Parameters
+/// - `overrides`, type `&ConfigOverrides`: the values specified on the command line
+fn read_config_file(overrides: &ConfigOverrides) -> HarTexResult<RawBotConfig> {
+    let (path, explicit) = match (&overrides.config_path, env::var_os("HARTEX_CONFIG")) {
+        (Some(path), _) => (path.clone(), true),
+        (None, Some(path)) => (PathBuf::from(path), true),
+        (None, None) => (PathBuf::from(DEFAULT_CONFIG_PATH), false)
+    };
+
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(error) if error.kind() == ErrorKind::NotFound && !explicit => return Ok(RawBotConfig::default()),
+        Err(error) => return Err(HarTexError::Custom {
+            message: format!("failed to read bot configuration file `{path}`: {error}", path = path.display())
+        })
+    };
+
+    toml::from_str(&source).map_err(|error| HarTexError::Custom {
+        message: format!("failed to parse bot configuration file `{path}`: {error}", path = path.display())
+    })
+}
+
+/// # Function `apply_env_overrides`
+///
+/// Overrides the values of the configuration with those of the environment variables that are
+/// set.
+///
+/// ## Parameters
+/// - `raw`, type `&mut RawBotConfig`: the configuration to override the values of
+fn apply_env_overrides(raw: &mut RawBotConfig) -> HarTexResult<()> {
+    env_override(&mut raw.token, "HARTEX_TOKEN")?;
+    env_override(&mut raw.application_id, "APPLICATION_ID")?;
+    env_override(&mut raw.global_administrator_id, "GLOBAL_ADMINISTRATOR_UID")?;
+    env_override(&mut raw.log_level, "HARTEX_LOG_LEVEL")?;
+    env_override(&mut raw.logging.file, "HARTEX_LOG_FILE")?;
+    env_override(&mut raw.logging.format, "HARTEX_LOG_FORMAT")?;
+    env_override(&mut raw.process_name, "HARTEX_PROCESS_NAME")?;
+    env_override(&mut raw.gateway.shards, "HARTEX_SHARDS")?;
+    env_override(&mut raw.gateway.sessions_file, "HARTEX_SESSIONS_FILE")?;
+    env_override(&mut raw.health.address, "HARTEX_HEALTH_ADDRESS")?;
+
+    let database = &mut raw.database;
+    env_override(&mut database.backend, "HARTEX_DATABASE_BACKEND")?;
+    env_override(&mut database.guildconfig_url, "PGSQL_CREDENTIALS_GUILDCONFIG")?;
+    env_override(&mut database.whitelist_url, "PGSQL_CREDENTIALS_GUILDS")?;
+
+    let pool = &mut database.pool;
+    env_override(&mut pool.max_connections, "PGSQL_POOL_MAX_CONNECTIONS")?;
+    env_override(&mut pool.min_connections, "PGSQL_POOL_MIN_CONNECTIONS")?;
+    env_override(&mut pool.connect_timeout_secs, "PGSQL_POOL_CONNECT_TIMEOUT_SECS")?;
+    env_override(&mut pool.idle_timeout_secs, "PGSQL_POOL_IDLE_TIMEOUT_SECS")?;
+    env_override(&mut pool.max_lifetime_secs, "PGSQL_POOL_MAX_LIFETIME_SECS")?;
+    env_override(&mut pool.test_before_acquire, "PGSQL_POOL_TEST_BEFORE_ACQUIRE")?;
+
+    Ok(())
+}
+
+/// # Function `apply_cli_overrides`
+///
+/// Overrides the values of the configuration with those specified on the command line.
+///
+/// ## Parameters
+/// - `raw`, type `&mut RawBotConfig`: the configuration to override the values of
+/// - `overrides`, type `&ConfigOverrides`: the values specified on the command line
+fn apply_cli_overrides(raw: &mut RawBotConfig, overrides: &ConfigOverrides) -> HarTexResult<()> {
+    if let Some(log_level) = &overrides.log_level {
+        raw.log_level = Some(log_level.clone());
+    }
+
+    if let Some(log_format) = &overrides.log_format {
+        raw.logging.format = Some(log_format.clone());
+    }
+
+    if let Some(backend) = &overrides.database_backend {
+        raw.database.backend = Some(backend.clone());
+    }
+
+    if let Some(shards) = &overrides.shards {
+        raw.gateway.shards = Some(shards.parse().map_err(|message| HarTexError::Custom {
+            message
+        })?);
+    }
+
+    if let Some(process_name) = &overrides.process_name {
+        raw.process_name = Some(process_name.clone());
+    }
+
+    Ok(())
+}
+
+/// # Function `validate`
+///
+/// Validates the configuration, collecting every problem found into a single error.
+///
+/// ## Parameters
+/// - `raw`, type `RawBotConfig`: the configuration to validate
+fn validate(raw: RawBotConfig) -> HarTexResult<BotConfig> {
+    let mut problems = Vec::new();
+
+    let token = raw.token.filter(|token| !token.is_empty());
+    if token.is_none() {
+        problems.push(String::from("the bot token is not set; set `token` or `HARTEX_TOKEN`"));
+    }
+
+    let application_id = raw.application_id.filter(|id| *id != 0);
+    if application_id.is_none() {
+        problems.push(String::from("the application id is not set; set `application_id` or `APPLICATION_ID`"));
+    }
+
+    let global_administrator_id = match raw.global_administrator_id {
+        Some(0) => {
+            problems.push(String::from("the global administrator id must be a positive integer"));
+            None
+        }
+        id => id.map(UserId)
+    };
+
+    let log_filter = raw.log_level
+        .map(|filter| filter.parse::<LogFilter>())
+        .transpose()
+        .unwrap_or_else(|problem| {
+            problems.push(problem);
+            None
+        })
+        .unwrap_or_default();
+
+    let log_format = raw.logging.format
+        .as_ref()
+        .map(|format| format.parse::<LogFormat>())
+        .transpose()
+        .unwrap_or_else(|problem| {
+            problems.push(problem);
+            None
+        })
+        .unwrap_or_default();
+    let log_sinks = validate_log_sinks(raw.logging, &mut problems);
+
+    let database = validate_database(raw.database, &mut problems);
+
+    let intents = raw.gateway.intents.map(|names| {
+        let mut intents = Intents::empty();
+
+        for name in names {
+            match INTENTS.iter().find(|(intent, _)| intent.eq_ignore_ascii_case(&name)) {
+                Some((_, intent)) => intents |= *intent,
+                None => problems.push(format!("unknown gateway intent `{name}`"))
+            }
+        }
+
+        intents
+    });
+
+    let event_types = validate_selection(raw.gateway.event_types, "gateway.event_types", &mut problems);
+    let resource_types = validate_selection(raw.cache.resource_types, "cache.resource_types", &mut problems);
+
+    let process_name = match (raw.process_name, &raw.gateway.shards) {
+        (Some(name), _) if name.trim().is_empty() => {
+            problems.push(String::from("the process name must not be empty"));
+            String::new()
+        }
+        (Some(name), _) => name,
+        (None, Some(RawShardRange { from, to, .. })) => format!("shards {from}-{to}"),
+        (None, None) => String::from("main")
+    };
+
+    let shard_scheme = match raw.gateway.shards {
+        Some(RawShardRange { from, to, total }) => {
+            if from > to || to >= total {
+                problems.push(format!("invalid shard range {from}..={to} of {total} shards; the range must satisfy `from <= to < total`"));
+            }
+
+            ShardScheme::Range {
+                from,
+                to,
+                total
+            }
+        }
+        None => ShardScheme::Auto
+    };
+
+    let sessions_file = PathBuf::from(raw.gateway.sessions_file.unwrap_or_else(|| {
+        let process = process_name
+            .chars()
+            .map(|character| if character.is_ascii_alphanumeric() || character == '-' || character == '_' { character } else { '_' })
+            .collect::<String>();
+
+        DEFAULT_SESSIONS_FILE.replace("{process}", &process)
+    }));
+
+    let health_address = raw.health.address.and_then(|address| match address.parse::<SocketAddr>() {
+        Ok(address) => Some(address),
+        Err(_) => {
+            problems.push(format!("invalid health server address `{address}`; expected an address such as `127.0.0.1:8080`"));
+            None
+        }
+    });
+
+    let presence = PresenceConfig {
+        status: match raw.presence.status.as_deref().unwrap_or("online") {
+            "online" => Status::Online,
+            "idle" => Status::Idle,
+            "dnd" => Status::DoNotDisturb,
+            "invisible" => Status::Invisible,
+            status => {
+                problems.push(format!("unknown presence status `{status}`; expected one of `online`, `idle`, `dnd` or `invisible`"));
+                Status::Online
+            }
+        },
+        activity_type: match raw.presence.activity_type.as_deref().unwrap_or("watching") {
+            "playing" => ActivityType::Playing,
+            "listening" => ActivityType::Listening,
+            "watching" => ActivityType::Watching,
+            "competing" => ActivityType::Competing,
+            activity_type => {
+                problems.push(format!("unknown activity type `{activity_type}`; expected one of `playing`, `listening`, `watching` or `competing`"));
+                ActivityType::Watching
+            }
+        },
+        activity_name: raw.presence.activity_name.unwrap_or_else(|| String::from("codebase revamp | shard {shard}"))
+    };
+
+    if !problems.is_empty() {
+        return Err(HarTexError::Custom {
+            message: format!("invalid bot configuration:\n - {problems}", problems = problems.join("\n - "))
+        });
+    }
+
+    // unwrapping here is fine as the missing values are reported as problems above
+    Ok(BotConfig {
+        token: token.unwrap(),
+        application_id: ApplicationId(application_id.unwrap()),
+        global_administrator_id,
+        log_filter,
+        log_sinks,
+        log_format,
+        process_name,
+        database,
+        intents,
+        event_types,
+        resource_types,
+        shard_scheme,
+        sessions_file,
+        health_address,
+        presence
+    })
+}
+
+/// # Function `validate_selection`
+///
+/// Validates a value that is either `derived` or `all`, defaulting to `derived`.
+///
+/// ## Parameters
+/// - `raw`, type `Option<String>`: the value to validate
+/// - `key`, type `&str`: the key of the value, used in the problem reported
+/// - `problems`, type `&mut Vec<String>`: the problems found so far
+fn validate_selection(raw: Option<String>, key: &str, problems: &mut Vec<String>) -> Selection {
+    match raw.as_deref().unwrap_or("derived") {
+        "derived" => Selection::Derived,
+        "all" => Selection::All,
+        value => {
+            problems.push(format!("invalid value `{value}` for `{key}`; expected `derived` or `all`"));
+            Selection::Derived
+        }
+    }
+}
+
+/// # Function `validate_log_sinks`
+///
+/// Validates the `[logging]` section of the configuration.
+///
+/// ## Parameters
+/// - `raw`, type `RawLoggingConfig`: the section to validate
+/// - `problems`, type `&mut Vec<String>`: the problems found so far
+fn validate_log_sinks(raw: RawLoggingConfig, problems: &mut Vec<String>) -> Vec<LogSinkConfig> {
+    let names = raw.sinks.unwrap_or_else(|| {
+        let mut names = vec![String::from("stdout")];
+
+        if raw.file.is_some() {
+            names.push(String::from("file"));
+        }
+
+        names
+    });
+
+    names
+        .iter()
+        .filter_map(|name| match name.as_str() {
+            "stdout" => Some(LogSinkConfig::Stdout),
+            "stderr" => Some(LogSinkConfig::Stderr),
+            "file" => match &raw.file {
+                Some(file) => Some(LogSinkConfig::File(PathBuf::from(file))),
+                None => {
+                    problems.push(String::from("the `file` log sink requires a file; set `logging.file` or `HARTEX_LOG_FILE`"));
+                    None
+                }
+            },
+            sink => {
+                problems.push(format!("unknown log sink `{sink}`; expected one of `stdout`, `stderr` or `file`"));
+                None
+            }
+        })
+        .collect()
+}
+
+/// # Function `validate_database`
+///
+/// Validates the `[database]` section of the configuration.
+///
+/// ## Parameters
+/// - `raw`, type `RawDatabaseConfig`: the section to validate
+/// - `problems`, type `&mut Vec<String>`: the problems found so far
+fn validate_database(raw: RawDatabaseConfig, problems: &mut Vec<String>) -> DatabaseConfig {
+    match raw.backend.as_deref().unwrap_or("postgres") {
+        "inmemory" => return DatabaseConfig::InMemory,
+        "postgres" => (),
+        backend => {
+            problems.push(format!("unknown database backend `{backend}`; expected `postgres` or `inmemory`"));
+            return DatabaseConfig::InMemory;
+        }
+    }
+
+    let guildconfig_url = raw.guildconfig_url.unwrap_or_else(|| {
+        problems.push(String::from("the guild configuration database URL is not set; set `database.guildconfig_url` or `PGSQL_CREDENTIALS_GUILDCONFIG`"));
+        String::new()
+    });
+    let whitelist_url = raw.whitelist_url.unwrap_or_else(|| {
+        problems.push(String::from("the whitelist database URL is not set; set `database.whitelist_url` or `PGSQL_CREDENTIALS_GUILDS`"));
+        String::new()
+    });
+
+    let default = PoolOptions::default();
+    let pool = PoolOptions {
+        max_connections: raw.pool.max_connections.unwrap_or(default.max_connections),
+        min_connections: raw.pool.min_connections.unwrap_or(default.min_connections),
+        connect_timeout: raw.pool.connect_timeout_secs.map(Duration::from_secs).unwrap_or(default.connect_timeout),
+        idle_timeout: raw.pool.idle_timeout_secs.map(Duration::from_secs).or(default.idle_timeout),
+        max_lifetime: raw.pool.max_lifetime_secs.map(Duration::from_secs).or(default.max_lifetime),
+        test_before_acquire: raw.pool.test_before_acquire.unwrap_or(default.test_before_acquire)
+    };
+
+    if pool.max_connections == 0 || pool.min_connections > pool.max_connections {
+        problems.push(format!(
+            "invalid pool size; `max_connections` ({max}) must be positive and at least `min_connections` ({min})",
+            min = pool.min_connections,
+            max = pool.max_connections
+        ));
+    }
+
+    DatabaseConfig::Postgres {
+        guildconfig_url,
+        whitelist_url,
+        pool
+    }
+}
+
+/// # Function `env_override`
+///
+/// Overrides a value of the configuration with the value of an environment variable, if it is
+/// set.
+///
+/// ## Parameters
+/// - `target`, type `&mut Option<T>`: the value to override
+/// - `key`, type `&str`: the environment variable
+fn env_override<T>(target: &mut Option<T>, key: &str) -> HarTexResult<()>
+where
+    T: FromStr {
+    if let Ok(value) = env::var(key) {
+        *target = Some(value.parse().map_err(|_| HarTexError::Custom {
+            message: format!("invalid value `{value}` for environment variable `{key}`")
+        })?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env,
+        fs
+    };
+
+    use hartex_core::error::HarTexError;
+
+    use hartex_logging::{
+        filter::LogFilter,
+        format::LogFormat,
+        level::LogLevel
+    };
+
+    use super::{
+        BotConfig,
+        ConfigOverrides,
+        RawBotConfig
+    };
+
+    /// # Constant `REQUIRED`
+    ///
+    /// The top-level values every valid configuration must have, with a database that needs no
+    /// credentials.
+    const REQUIRED: &str = "token = \"token\"\napplication_id = 1\n\n[database]\nbackend = \"inmemory\"\n";
+
+    /// # Function `problems_of`
+    ///
+    /// Returns the problems reported when validating the configuration.
+    fn problems_of(source: &str) -> String {
+        let raw = toml::from_str::<RawBotConfig>(source).unwrap();
+
+        match super::validate(raw) {
+            Err(HarTexError::Custom { message }) => message,
+            Err(_) => panic!("expected the problems to be reported as a custom error"),
+            Ok(_) => panic!("expected the configuration to be invalid")
+        }
+    }
+
+    #[test]
+    fn later_layers_override_earlier_ones() {
+        let path = env::temp_dir().join(format!("hartex_config_test_{id}.toml", id = std::process::id()));
+        fs::write(&path, format!("log_level = \"error\"\nprocess_name = \"file\"\n{REQUIRED}\n[logging]\nformat = \"json\"\n\n[health]\naddress = \"127.0.0.1:8080\"\n")).unwrap();
+
+        // no other test sets these environment variables
+        env::set_var("HARTEX_LOG_LEVEL", "warn");
+        env::set_var("HARTEX_LOG_FORMAT", "text");
+        env::set_var("HARTEX_PROCESS_NAME", "environment");
+
+        let config = BotConfig::load(&ConfigOverrides {
+            config_path: Some(path.clone()),
+            process_name: Some(String::from("command line")),
+            ..ConfigOverrides::default()
+        });
+
+        env::remove_var("HARTEX_LOG_LEVEL");
+        env::remove_var("HARTEX_LOG_FORMAT");
+        env::remove_var("HARTEX_PROCESS_NAME");
+        fs::remove_file(&path).unwrap();
+
+        let config = match config {
+            Ok(config) => config,
+            Err(_) => panic!("expected the configuration to be valid")
+        };
+
+        assert_eq!(config.health_address, Some(([127, 0, 0, 1], 8080).into()));
+        assert_eq!(config.log_filter, LogFilter::new(LogLevel::Warn));
+        assert_eq!(config.log_format, LogFormat::Text);
+        assert_eq!(config.process_name, "command line");
+    }
+
+    #[test]
+    fn command_line_overrides_are_validated() {
+        let mut raw = RawBotConfig::default();
+        let overrides = ConfigOverrides {
+            shards: Some(String::from("0-1")),
+            ..ConfigOverrides::default()
+        };
+
+        assert!(super::apply_cli_overrides(&mut raw, &overrides).is_err());
+    }
+
+    #[test]
+    fn rejects_an_invalid_log_filter() {
+        let problems = problems_of(&format!("log_level = \"info,hartex_dbmani=\"\n{REQUIRED}"));
+
+        assert!(problems.contains("unknown log level ``"), "{problems}");
+    }
+
+    #[test]
+    fn rejects_an_invalid_health_address() {
+        let problems = problems_of(&format!("{REQUIRED}\n[health]\naddress = \"localhost\"\n"));
+
+        assert!(problems.contains("invalid health server address `localhost`"), "{problems}");
+    }
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let problems = problems_of("log_level = \"loud\"\n\n[health]\naddress = \"localhost\"\n\n[database]\nbackend = \"inmemory\"\n");
+
+        assert!(problems.contains("the bot token is not set"), "{problems}");
+        assert!(problems.contains("the application id is not set"), "{problems}");
+        assert!(problems.contains("unknown log level `loud`"), "{problems}");
+        assert!(problems.contains("invalid health server address `localhost`"), "{problems}");
+    }
+}
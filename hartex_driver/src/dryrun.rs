@@ -0,0 +1,249 @@
+//! # The `dryrun` Module
+//!
+//! This module implements the stand-ins for the side effects of the event handlers, used when
+//! replaying events without `--live`:
+//!
+//! - a local HTTP server that the Twilight HTTP client is pointed at instead of the Discord API,
+//!   which records every request and refuses it;
+//! - a repository that reads from the configured repository, but only logs the writes.
+
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        Arc,
+        Mutex
+    }
+};
+
+use hyper::{
+    header,
+    service::{
+        make_service_fn,
+        service_fn
+    },
+    Body,
+    Request,
+    Response,
+    Server,
+    StatusCode
+};
+
+use tokio::{
+    sync::oneshot,
+    task::JoinHandle
+};
+
+use hartex_conftoml::MigratedConfig;
+
+use hartex_core::{
+    discord::{
+        http::Client,
+        model::id::{
+            GuildId,
+            UserId
+        }
+    },
+    error::{
+        HarTexError,
+        HarTexResult
+    }
+};
+
+use hartex_dbmani::{
+    repository::{
+        Repository,
+        SharedRepository
+    },
+    whitelist::model::WhitelistedGuild
+};
+
+use hartex_logging::{
+    error,
+    info
+};
+
+use hartex_utils::FutureRetType;
+
+use crate::config::BotConfig;
+
+/// # Constant `REFUSAL`
+///
+/// The body of the responses of the recording server, in the format of the errors of the Discord
+/// API.
+const REFUSAL: &str = r#"{"code":0,"message":"not sent to Discord; replaying without --live"}"#;
+
+/// # Struct `RecordingServer`
+///
+/// A local HTTP server recording the requests made to the Discord API, and refusing them.
+pub struct RecordingServer {
+    address: SocketAddr,
+    requests: Arc<Mutex<Vec<String>>>,
+    shutdown: oneshot::Sender<()>,
+    handle: JoinHandle<()>
+}
+
+impl RecordingServer {
+    /// # Static Method `RecordingServer::start`
+    ///
+    /// Starts the recording server on a free local port.
+    pub fn start() -> HarTexResult<Self> {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+
+        let make_service = make_service_fn(move |_| {
+            let recorded = recorded.clone();
+
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let request = format!("{method} {path}", method = request.method(), path = request.uri().path());
+
+                    info!("not sending request `{request}` to Discord; replaying without `--live`");
+
+                    // the lock is never held across a panic
+                    recorded.lock().unwrap().push(request);
+
+                    async move {
+                        Ok::<_, Infallible>(
+                            Response::builder()
+                                .status(StatusCode::SERVICE_UNAVAILABLE)
+                                .header(header::CONTENT_TYPE, "application/json")
+                                .body(Body::from(REFUSAL))
+                                .unwrap()
+                        )
+                    }
+                }))
+            }
+        });
+
+        let server = Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+            .map_err(|error| HarTexError::Custom {
+                message: format!("failed to bind recording server: {error}")
+            })?
+            .serve(make_service);
+        let address = server.local_addr();
+
+        let (shutdown, receiver) = oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            let graceful = server.with_graceful_shutdown(async {
+                // the server shuts down when the sender is either used or dropped
+                let _ = receiver.await;
+            });
+
+            if let Err(error) = graceful.await {
+                error!("recording server failed: {error}");
+            }
+        });
+
+        Ok(Self {
+            address,
+            requests,
+            shutdown,
+            handle
+        })
+    }
+
+    /// # Instance Method `RecordingServer::http_client`
+    ///
+    /// Builds a Twilight HTTP client that makes its requests to the recording server. The client
+    /// is given neither the token of the bot nor a ratelimiter.
+    ///
+    /// ## Parameters
+    /// - `config`, type `&BotConfig`: the bot-level configuration
+    pub fn http_client(&self, config: &BotConfig) -> Client {
+        Client::builder()
+            .application_id(config.application_id)
+            .proxy(self.address.to_string(), true)
+            .ratelimiter(None)
+            .build()
+    }
+
+    /// # Asynchronous Instance Method `RecordingServer::stop`
+    ///
+    /// Stops the recording server, returning the requests it has recorded, in the order they were
+    /// made.
+    pub async fn stop(self) -> Vec<String> {
+        let _ = self.shutdown.send(());
+        let _ = self.handle.await;
+
+        // the lock is never held across a panic
+        let requests = self.requests.lock().unwrap();
+
+        requests.clone()
+    }
+}
+
+/// # Struct `ReadOnlyRepository`
+///
+/// A repository reading from another repository, and only logging the writes. The writes report
+/// what they would have done.
+pub struct ReadOnlyRepository {
+    inner: SharedRepository
+}
+
+impl ReadOnlyRepository {
+    /// # Constructor `ReadOnlyRepository::new`
+    ///
+    /// Creates a new `ReadOnlyRepository` reading from the provided repository.
+    ///
+    /// ## Parameters
+    /// - `inner`, type `SharedRepository`: the repository to read from
+    pub fn new(inner: SharedRepository) -> Self {
+        Self {
+            inner
+        }
+    }
+}
+
+impl Repository for ReadOnlyRepository {
+    fn guild_config<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, MigratedConfig> {
+        self.inner.guild_config(guild_id)
+    }
+
+    fn guild_config_source<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, Option<String>> {
+        self.inner.guild_config_source(guild_id)
+    }
+
+    fn update_guild_config<'asynchronous_trait>(&self, guild_id: GuildId, _: String) -> FutureRetType<'asynchronous_trait, ()> {
+        info!(guild_id = guild_id; "not updating the configuration of the guild; replaying without `--live`");
+
+        Box::pin(async { Ok(()) })
+    }
+
+    fn whitelisted_guilds<'asynchronous_trait>(&self) -> FutureRetType<'asynchronous_trait, Vec<WhitelistedGuild>> {
+        self.inner.whitelisted_guilds()
+    }
+
+    fn add_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId, _: String, _: UserId) -> FutureRetType<'asynchronous_trait, ()> {
+        info!(guild_id = guild_id; "not whitelisting the guild; replaying without `--live`");
+
+        Box::pin(async { Ok(()) })
+    }
+
+    fn deactivate_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId, _: String) -> FutureRetType<'asynchronous_trait, bool> {
+        info!(guild_id = guild_id; "not deactivating the whitelist of the guild; replaying without `--live`");
+
+        Box::pin(is_whitelisted(self.inner.clone(), guild_id))
+    }
+
+    fn remove_whitelisted_guild<'asynchronous_trait>(&self, guild_id: GuildId) -> FutureRetType<'asynchronous_trait, bool> {
+        info!(guild_id = guild_id; "not removing the whitelist of the guild; replaying without `--live`");
+
+        Box::pin(is_whitelisted(self.inner.clone(), guild_id))
+    }
+}
+
+/// # Asynchronous Function `is_whitelisted`
+///
+/// Returns whether a guild is whitelisted, i.e. whether its whitelist would be modified.
+///
+/// ## Parameters
+/// - `repository`, type `SharedRepository`: the repository to retrieve the whitelisted guilds from
+/// - `guild_id`, type `GuildId`: the guild id to look for
+async fn is_whitelisted(repository: SharedRepository, guild_id: GuildId) -> HarTexResult<bool> {
+    Ok(repository
+        .whitelisted_guilds()
+        .await?
+        .iter()
+        .any(|guild| guild.GuildId == guild_id.0))
+}
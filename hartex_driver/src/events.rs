@@ -3,15 +3,10 @@
 //! The `events` module provides utility functions for handling events as they come into the event
 //! loop.
 
+use hartex_cmdsys::featuregate;
+
 use hartex_core::{
-    discord::{
-        gateway::{
-            Cluster,
-            Event
-        },
-        http::Client,
-        cache_inmemory::InMemoryCache
-    },
+    discord::gateway::Event,
     error::{
         HarTexError,
         HarTexResult
@@ -19,12 +14,12 @@ use hartex_core::{
     events::EventType
 };
 
-use hartex_eventsys::{
-    emitter::EventEmitter,
-    events::HarTexEvent
-};
+use hartex_eventsys::events::HarTexEvent;
 
-use crate::handler::EventHandler;
+use crate::{
+    handler::EventHandler,
+    services::BotServices
+};
 
 /// # Asynchronous Function `handle_event`
 ///
@@ -39,34 +34,43 @@ use crate::handler::EventHandler;
 /// - `custom`, type `Option<HarTexEvent>`: the custom event; should only be set to `Some(...)`
 ///                                         when the `event_type` parameter is set to
 ///                                         `EventType::Custom`
-/// - `http`, type `Client`: the Twilight HTTP Client to use for some specific events that need it
-/// - `cluster`, type `Cluster`: the gateway cluster to use for some specific events that need it
+/// - `services`, type `BotServices`: the services to use for some specific events that need them
 #[allow(clippy::needless_lifetimes)]
 pub async fn handle_event(
     (event_type, twilight, custom): (EventType, Option<Event>, Option<HarTexEvent>),
-    http: Client,
-    emitter: EventEmitter,
-    cache: InMemoryCache,
-    cluster: Cluster
+    services: BotServices
 ) -> HarTexResult<()> {
     match event_type {
         EventType::Twilight if twilight.is_some() => {
-            match twilight.unwrap() {
+            let twilight = twilight.unwrap();
+
+            // the handlers requiring a nightly feature are skipped for guilds that have not opted
+            // in to it
+            if let Some((feature, guild_id)) = EventHandler::required_nightly_feature(&twilight) {
+                if !featuregate::is_enabled(&services.config_cache, guild_id, Some(feature)).await {
+                    return Ok(());
+                }
+            }
+
+            match twilight {
                 Event::GuildCreate(payload) => {
-                    EventHandler::guild_create(payload, http).await?
+                    EventHandler::guild_create(payload, services.http, services.repository).await?
                 }
                 Event::InteractionCreate(payload) => {
-                    EventHandler::interaction_create(payload, http, cluster, cache).await?
+                    EventHandler::interaction_create(payload, services).await?
                 }
                 Event::MessageCreate(payload) => {
-                    EventHandler::message_create(payload, emitter, cache, http, cluster).await?
+                    EventHandler::message_create(payload, services.emitter, services.cache, services.http, services.cluster).await?
                 }
                 Event::Ready(payload) => {
-                    EventHandler::ready(payload, cluster, http).await?
+                    EventHandler::ready(payload, services.cluster, services.config).await?
                 }
                 Event::ShardIdentifying(payload) => {
                     EventHandler::shard_identifying(payload).await?
                 }
+                Event::ThreadCreate(payload) => {
+                    EventHandler::thread_create(payload.0, services.http).await?
+                }
                 _ => ()
             }
         },
@@ -75,6 +79,12 @@ pub async fn handle_event(
                 HarTexEvent::CommandExecuted(payload) => {
                     EventHandler::command_executed(payload).await?
                 }
+                HarTexEvent::ConfigurationReloaded(payload) => {
+                    EventHandler::configuration_reloaded(payload, services.http, services.config_cache).await?
+                }
+                HarTexEvent::ConfigurationUpdated(payload) => {
+                    EventHandler::configuration_updated(payload, services.config_cache).await?
+                }
             }
         }
         _ => return Err(HarTexError::Custom {
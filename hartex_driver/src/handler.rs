@@ -3,27 +3,32 @@
 //! This module defines the `EventHandler` struct, which defines various function handlers for
 //! individual events.
 
-use tokio::time;
+use std::sync::Arc;
+
+use hartex_conftoml::nightly::NightlyFeature;
 
 use hartex_core::{
     discord::{
         cache_inmemory::InMemoryCache,
-        gateway::Cluster,
+        gateway::{
+            Cluster,
+            Event
+        },
         http::Client,
-        model::gateway::{
-            event::shard::Identifying,
-            payload::{
-                update_presence::UpdatePresence,
-                GuildCreate,
-                InteractionCreate,
-                MessageCreate,
-                Ready,
+        model::{
+            channel::Channel,
+            gateway::{
+                event::shard::Identifying,
+                payload::{
+                    update_presence::UpdatePresence,
+                    GuildCreate,
+                    InteractionCreate,
+                    MessageCreate,
+                    Ready,
+                },
+                presence::Activity
             },
-            presence::{
-                Activity,
-                ActivityType,
-                Status
-            }
+            id::GuildId
         }
     },
     error::{
@@ -33,27 +38,30 @@ use hartex_core::{
 };
 
 use hartex_dbmani::{
-    guildconf::GetGuildConfig,
-    whitelist::GetWhitelistedGuilds
+    confcache::GuildConfigCache,
+    repository::SharedRepository
 };
 
 use hartex_eventsys::emitter::EventEmitter;
 
-use hartex_logging::Logger;
+use hartex_logging::{
+    error,
+    info,
+    verbose
+};
 
-use hartex_model::payload::CommandExecuted;
+use hartex_metrics::http::RecordHttp;
 
-use hartex_plugins::{
-    global::{
-        about::About,
-        ping::Ping,
-        source::Source,
-        team::Team
-    },
-    information::userinfo::Userinfo
+use hartex_model::payload::{
+    CommandExecuted,
+    ConfigurationReloaded,
+    ConfigurationUpdated
 };
 
-use crate::commands;
+use crate::{
+    config::BotConfig,
+    services::BotServices
+};
 
 /// # Struct `EventHandler`
 ///
@@ -73,82 +81,64 @@ impl EventHandler {
     ///                          in the whitelist or that the whitelist has been removed, or that
     ///                          the guild has been previously been whitelisted but the whitelist
     ///                          is deactivated with a reason.
-    pub async fn guild_create(payload: Box<GuildCreate>, http: Client) -> HarTexResult<()> {
+    /// - `repository`, type `SharedRepository`: the repository to retrieve the whitelisted guilds
+    ///                                          from
+    pub async fn guild_create(payload: Box<GuildCreate>, http: Client, repository: SharedRepository) -> HarTexResult<()> {
         let guild_id = payload.id;
 
-        Logger::verbose(
-            format!("joined a new guild with name `{name}` with id {guild_id}; checking whether the guild is whitelisted", name = payload.name),
-            Some(module_path!()),
-            file!(),
-            line!(),
-            column!()
-        );
-
-        let res = GetWhitelistedGuilds::default().await?;
+        verbose!(guild_id = guild_id; "joined a new guild with name `{name}`; checking whether the guild is whitelisted", name = payload.name);
 
-        if !res.iter().any(|guild| {
+        let res = repository.whitelisted_guilds().await?;
+        let whitelist = res.iter().find(|guild| {
             guild_id.0 == guild.GuildId
-        }) {
-            Logger::error(
-                "guild is not whitelisted",
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
-
-            let guild = http.guild(guild_id).exec().await?.model().await?;
-
-            Logger::verbose(
-                "dming guild owner about the whitelist status",
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+        });
+
+        if !whitelist.map(|guild| guild.is_active()).unwrap_or(false) {
+            error!(guild_id = guild_id; "guild is not whitelisted or its whitelist is deactivated");
+
+            let guild = http.guild(guild_id).exec().recorded("guild").await?.model().await?;
+
+            verbose!(guild_id = guild_id; "dming guild owner about the whitelist status");
 
             let guild_owner = guild.owner_id;
 
-            let user = http.user(guild_owner).exec().await?.model().await?;
-
-            let dm_channel = http.create_private_channel(user.id).exec().await?.model().await?;
-            let message = "Hey there! It looks like you added HarTex to your guild by the name of \"".to_string()
-                + &guild.name + "\".\n\n"
-                + "Unfortunately, your guild has not been whitelisted yet and the bot cannot be "
-                + "invited to your guild until you apply for a whitelist and that the application is "
-                + "accepted.\n\n"
-                + "You may apply for a guild whitelist if your guild meets the following criteria, they include, but not limited to:\n"
-                + " - guild member count of at least 250;"
-                + " - be always abide by the Discord Terms of Service (<https://discord.com/terms>) and Community Guidelines (<https://discord.com/guidelines);"
-                + " - how old is the guild and/or how active is it; and"
-                + " - your experience level with TOML to configure the bot before using it.\n\n"
-                + "You may join our Support Guild at <discord.gg/s8qjxZK> for more information, including the application link in which you may use"
-                + "to apply for a whitelist application. Good luck!";
-
-            http.create_message(dm_channel.id).content(&message)?.exec().await?;
-
-            Logger::error(
-                "leaving guild",
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
-
-            http.leave_guild(guild_id).exec().await?;
+            let user = http.user(guild_owner).exec().recorded("user").await?.model().await?;
+
+            let dm_channel = http.create_private_channel(user.id).exec().recorded("create_private_channel").await?.model().await?;
+            let message = if let Some(whitelist) = whitelist {
+                "Hey there! It looks like you added HarTex to your guild by the name of \"".to_string()
+                    + &guild.name + "\".\n\n"
+                    + "Unfortunately, the whitelist of your guild has been deactivated for the following reason:\n"
+                    + "> " + whitelist.Reason.as_deref().unwrap_or("no reason provided") + "\n\n"
+                    + "You may join our Support Guild at <discord.gg/s8qjxZK> if you would like to appeal the deactivation."
+            }
+            else {
+                "Hey there! It looks like you added HarTex to your guild by the name of \"".to_string()
+                    + &guild.name + "\".\n\n"
+                    + "Unfortunately, your guild has not been whitelisted yet and the bot cannot be "
+                    + "invited to your guild until you apply for a whitelist and that the application is "
+                    + "accepted.\n\n"
+                    + "You may apply for a guild whitelist if your guild meets the following criteria, they include, but not limited to:\n"
+                    + " - guild member count of at least 250;"
+                    + " - be always abide by the Discord Terms of Service (<https://discord.com/terms>) and Community Guidelines (<https://discord.com/guidelines);"
+                    + " - how old is the guild and/or how active is it; and"
+                    + " - your experience level with TOML to configure the bot before using it.\n\n"
+                    + "You may join our Support Guild at <discord.gg/s8qjxZK> for more information, including the application link in which you may use"
+                    + "to apply for a whitelist application. Good luck!"
+            };
+
+            http.create_message(dm_channel.id).content(&message)?.exec().recorded("create_message").await?;
+
+            error!(guild_id = guild_id; "leaving guild");
+
+            http.leave_guild(guild_id).exec().recorded("leave_guild").await?;
 
             return Err(HarTexError::Custom {
                 message: String::from("guild is not whitelisted")
             });
         }
 
-        Logger::info(
-            "guild is whitelisted",
-            Some(module_path!()),
-            file!(),
-            line!(),
-            column!()
-        );
+        info!(guild_id = guild_id; "guild is whitelisted");
 
         Ok(())
     }
@@ -159,14 +149,10 @@ impl EventHandler {
     ///
     /// ## Parameters
     /// - `payload`, type `Box<InteractionCreate>`: the `InteractionCreate` event payload
-    /// - `http`, type `Client`: the Twilight HTTP client to pass to the command if the message is indeed a command
-    pub async fn interaction_create(
-        payload: Box<InteractionCreate>,
-        http: Client,
-        cluster: Cluster,
-        cache: InMemoryCache
-    ) -> HarTexResult<()> {
-        crate::interactions::handle_interaction(payload.0, cache, http, cluster).await?;
+    /// - `services`, type `BotServices`: the services to pass to the command if the interaction is
+    ///                                   indeed a command
+    pub async fn interaction_create(payload: Box<InteractionCreate>, services: BotServices) -> HarTexResult<()> {
+        crate::interactions::handle_interaction(payload.0, services).await?;
 
         Ok(())
     }
@@ -197,34 +183,22 @@ impl EventHandler {
     /// ## Parameters
     /// - `payload`, type `Box<Ready>`: the `Ready` event payload
     /// - `cluster`, type `Cluster`: the gateway cluster
-    /// - `http`, type `Client`: the http client
-    pub async fn ready(payload: Box<Ready>, cluster: Cluster, http: Client) -> HarTexResult<()> {
+    /// - `config`, type `Arc<BotConfig>`: the bot-level configuration to obtain the presence from
+    pub async fn ready(payload: Box<Ready>, cluster: Cluster, config: Arc<BotConfig>) -> HarTexResult<()> {
         let user = payload.user;
 
-        Logger::info(
-            format!(
-                "{}#{} [id: {}] has successfully startup; using discord api v{}",
-                user.name,
-                user.discriminator,
-                user.id,
-                payload.version
-            ),
-            Some(module_path!()),
-            file!(),
-            line!(),
-            column!()
+        info!(
+            "{}#{} [id: {}] has successfully startup; using discord api v{}",
+            user.name,
+            user.discriminator,
+            user.id,
+            payload.version
         );
 
         for shard in cluster.shards() {
             let shard_id = shard.info()?.id();
 
-            Logger::verbose(
-                format!("registering presence for shard {shard_id}"),
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+            verbose!(shard_id = shard_id; "registering presence");
 
             match shard.command(
                 &UpdatePresence::new(
@@ -238,8 +212,8 @@ impl EventHandler {
                         flags: None,
                         id: None,
                         instance: None,
-                        kind: ActivityType::Watching,
-                        name: format!("codebase revamp | shard {}", shard_id),
+                        kind: config.presence.activity_type,
+                        name: config.presence.activity_name_for(shard_id, &config.process_name),
                         party: None,
                         secrets: None,
                         state: None,
@@ -248,70 +222,35 @@ impl EventHandler {
                     }],
                     false,
                     None,
-                    Status::Online
+                    config.presence.status
                 )?
             ).await {
                 Ok(()) => {
-                    Logger::verbose(
-                        format!("successfully set presence for shard {shard_id}"),
-                        Some(module_path!()),
-                        file!(),
-                        line!(),
-                        column!()
-                    );
+                    verbose!(shard_id = shard_id; "successfully set presence");
                 },
                 Err(error) => {
-                    Logger::error(
-                        format!("failed to set presence for shard {shard_id}: {error}"),
-                        Some(module_path!()),
-                        file!(),
-                        line!(),
-                        column!()
-                    );
+                    error!(shard_id = shard_id; "failed to set presence: {error}");
                 }
             }
         }
 
-        commands::register_global_commands(
-            vec![
-                // Global Plugin
-                Box::new(About),
-                Box::new(Ping),
-                Box::new(Source),
-                Box::new(Team),
-
-                // Information Plugin
-                Box::new(Userinfo)
-            ],
-            http.clone()
-        ).await?;
-
-        for guild in http.current_user_guilds().exec().await?.models().await? {
-            Logger::verbose(
-                format!("changing nickname in guild {name}", name = guild.name),
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
-
-            let config = GetGuildConfig::new(guild.id).await?;
-
-            match http.update_current_user_nick(guild.id, &config.GuildConfiguration.nickname).exec().await {
-                Err(error) => {
-                    Logger::error(
-                        format!("failed to change nickname: {error}"),
-                        Some(module_path!()),
-                        file!(),
-                        line!(),
-                        column!()
-                    );
-                }
-                _ => ()
-            };
+        Ok(())
+    }
 
-            time::sleep(time::Duration::from_secs(1)).await;
-        }
+    /// # Static Asynchronous Method `EventHandler::thread_create`
+    ///
+    /// Handles the `ThreadCreate` event; joins the created thread, so that the events of the
+    /// thread are received. Requires the `threads` nightly feature.
+    ///
+    /// ## Parameters
+    /// - `thread`, type `Channel`: the created thread
+    /// - `http`, type `Client`: the Twilight HTTP client to use for joining the thread
+    pub async fn thread_create(thread: Channel, http: Client) -> HarTexResult<()> {
+        let thread_id = thread.id();
+
+        verbose!("joining newly created thread {thread_id}");
+
+        http.join_thread(thread_id).exec().recorded("join_thread").await?;
 
         Ok(())
     }
@@ -324,21 +263,31 @@ impl EventHandler {
     ///
     /// - `payload`, type `Identifying`: the `Identifying` event payload
     pub async fn shard_identifying(payload: Identifying) -> HarTexResult<()> {
-        Logger::verbose(
-            format!(
-                "shard {} is identifying with the discord gateway",
-                payload.shard_id
-            ),
-            Some(module_path!()),
-            file!(),
-            line!(),
-            column!()
+        verbose!(
+            "shard {} is identifying with the discord gateway",
+            payload.shard_id
         );
 
         Ok(())
     }
 }
 
+impl EventHandler {
+    /// # Static Method `EventHandler::required_nightly_feature`
+    ///
+    /// Returns the nightly feature that the handler of a twilight event requires, if any, along
+    /// with the guild the event is received from.
+    ///
+    /// ## Parameters
+    /// - `event`, type `&Event`: the twilight event
+    pub fn required_nightly_feature(event: &Event) -> Option<(NightlyFeature, Option<GuildId>)> {
+        match event {
+            Event::ThreadCreate(payload) => Some((NightlyFeature::Threads, payload.0.guild_id())),
+            _ => None
+        }
+    }
+}
+
 // Custom Events
 // TODO: actually make use of these events
 impl EventHandler {
@@ -350,12 +299,54 @@ impl EventHandler {
     ///
     /// - `payload`, type `Box<CommandExecuted>`: the `CommandExecuted` event payload
     pub async fn command_executed(payload: Box<CommandExecuted>) -> HarTexResult<()> {
-        Logger::info(
-            format!("command `{command}` is executed in guild {guild}", command = payload.command, guild = payload.guild_id),
-            Some(module_path!()),
-            file!(),
-            line!(),
-            column!()
+        info!(command = payload.command, guild_id = payload.guild_id; "command is executed");
+
+        Ok(())
+    }
+
+    /// # Static Asynchronous Method `EventHandler::configuration_reloaded`
+    ///
+    /// Handles the `ConfigurationReloaded` event; re-applies the side effects of the configuration,
+    /// such as the nickname of the bot in the guild.
+    ///
+    /// ## Parameters
+    ///
+    /// - `payload`, type `Box<ConfigurationReloaded>`: the `ConfigurationReloaded` event payload
+    /// - `http`, type `Client`: the Twilight HTTP client to use for re-applying the side effects
+    /// - `config_cache`, type `GuildConfigCache`: the guild configuration cache holding the
+    ///                                            reloaded configuration
+    pub async fn configuration_reloaded(payload: Box<ConfigurationReloaded>, http: Client, config_cache: GuildConfigCache) -> HarTexResult<()> {
+        let guild_id = payload.guild_id;
+        let config = config_cache.get(guild_id).await?;
+
+        verbose!(guild_id = guild_id; "configuration of the guild is reloaded; re-applying nickname");
+
+        if let Err(error) = http.update_current_user_nick(guild_id, &config.GuildConfiguration.nickname).exec().recorded("update_current_user_nick").await {
+            error!(guild_id = guild_id; "failed to change nickname: {error}");
+        }
+
+        Ok(())
+    }
+
+    /// # Static Asynchronous Method `EventHandler::configuration_updated`
+    ///
+    /// Handles the `ConfigurationUpdated` event.
+    ///
+    /// ## Parameters
+    ///
+    /// - `payload`, type `Box<ConfigurationUpdated>`: the `ConfigurationUpdated` event payload
+    /// - `config_cache`, type `GuildConfigCache`: the guild configuration cache to invalidate the
+    ///                                            configuration of the guild from
+    pub async fn configuration_updated(payload: Box<ConfigurationUpdated>, config_cache: GuildConfigCache) -> HarTexResult<()> {
+        config_cache.invalidate(payload.guild_id);
+
+        let metrics = config_cache.metrics();
+
+        verbose!(
+            "configuration of guild {guild} is updated; guild configuration cache has {size} entries, hit rate {hit_rate:.2}",
+            guild = payload.guild_id,
+            size = metrics.size,
+            hit_rate = metrics.hit_rate()
         );
 
         Ok(())
@@ -0,0 +1,568 @@
+//! # The `health` Module
+//!
+//! This module implements the optional health server of the bot, a local HTTP server for the
+//! orchestrator running the bot to check on it. It serves the following endpoints:
+//!
+//! - `GET /healthz`: always `200 OK` while the process is up;
+//! - `GET /readyz`: `200 OK` if all shards are connected and the database is reachable, and
+//!   `503 Service Unavailable` otherwise;
+//! - `GET /shards`: the status of every shard as JSON, with their connection state, latency and
+//!   number of guilds;
+//! - `GET /metrics`: the metrics of the bot, in the Prometheus text format.
+//!
+//! The shard statuses and the cache sizes are obtained from a `ShardStatusSource` and a
+//! `CacheSizeSource`, so that the server can be used with fake ones without connecting to Discord.
+
+use std::{
+    collections::{
+        HashMap,
+        HashSet
+    },
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        Arc,
+        Mutex
+    },
+    time::Duration
+};
+
+use hyper::{
+    header,
+    service::{
+        make_service_fn,
+        service_fn
+    },
+    Body,
+    Method,
+    Request,
+    Response,
+    Server,
+    StatusCode
+};
+
+use serde::Serialize;
+
+use tokio::{
+    sync::oneshot,
+    task::JoinHandle
+};
+
+use hartex_core::{
+    discord::{
+        cache_inmemory::InMemoryCache,
+        gateway::{
+            Cluster,
+            Event
+        },
+        model::id::GuildId
+    },
+    error::{
+        HarTexError,
+        HarTexResult
+    }
+};
+
+use hartex_dbmani::{
+    confcache::GuildConfigCache,
+    pool::PoolManager
+};
+
+use hartex_logging::{
+    error,
+    info
+};
+
+/// # Constant `CONNECTED`
+///
+/// The state of a shard that is connected to the gateway.
+pub const CONNECTED: &str = "connected";
+
+/// # Struct `ShardStatus`
+///
+/// The status of a shard, as reported by the `/shards` endpoint.
+#[derive(Clone, Debug, Serialize)]
+pub struct ShardStatus {
+    pub id: u64,
+    /// The connection state of the shard; `connected` once it is ready to receive events.
+    pub state: String,
+    /// The average latency of the heartbeats of the shard, in milliseconds.
+    pub latency_ms: Option<u128>,
+    pub guilds: usize
+}
+
+/// # Trait `ShardStatusSource`
+///
+/// A source of the statuses of the shards run by the process.
+///
+/// ## Trait Methods
+/// - `shard_statuses`; return type `Vec<ShardStatus>`: the statuses of the shards
+pub trait ShardStatusSource {
+    fn shard_statuses(&self) -> Vec<ShardStatus>;
+}
+
+/// # Trait `CacheSizeSource`
+///
+/// A source of the sizes of the caches of the process.
+///
+/// ## Trait Methods
+/// - `cache_sizes`; return type `Vec<(&'static str, usize)>`: the number of entries of every
+///                  cached resource
+pub trait CacheSizeSource {
+    fn cache_sizes(&self) -> Vec<(&'static str, usize)>;
+}
+
+/// # Struct `GuildCounter`
+///
+/// Keeps track of the guilds of every shard from the gateway events they receive.
+#[derive(Clone, Default)]
+pub struct GuildCounter {
+    guilds: Arc<Mutex<HashMap<u64, HashSet<GuildId>>>>
+}
+
+impl GuildCounter {
+    /// # Instance Method `GuildCounter::update`
+    ///
+    /// Updates the guilds of a shard with an event received by it.
+    ///
+    /// ## Parameters
+    /// - `shard_id`, type `u64`: the id of the shard that received the event
+    /// - `event`, type `&Event`: the event
+    pub fn update(&self, shard_id: u64, event: &Event) {
+        // the lock is never held across a panic
+        let mut guilds = self.guilds.lock().unwrap();
+
+        match event {
+            // the guilds of the shard are sent as `GuildCreate` events after it is ready
+            Event::Ready(_) => {
+                guilds.insert(shard_id, HashSet::new());
+            }
+            Event::GuildCreate(payload) => {
+                guilds.entry(shard_id).or_default().insert(payload.id);
+            }
+            // a guild that becomes unavailable during an outage still belongs to the shard
+            Event::GuildDelete(payload) if !payload.unavailable => {
+                guilds.entry(shard_id).or_default().remove(&payload.id);
+            }
+            _ => ()
+        }
+    }
+
+    /// # Instance Method `GuildCounter::count`
+    ///
+    /// Returns the number of guilds of a shard.
+    ///
+    /// ## Parameters
+    /// - `shard_id`, type `u64`: the id of the shard
+    pub fn count(&self, shard_id: u64) -> usize {
+        self.guilds.lock().unwrap().get(&shard_id).map(HashSet::len).unwrap_or(0)
+    }
+}
+
+/// # Struct `ClusterStatus`
+///
+/// The statuses of the shards of the gateway cluster.
+pub struct ClusterStatus {
+    cluster: Cluster,
+    guilds: GuildCounter
+}
+
+impl ClusterStatus {
+    /// # Constructor `ClusterStatus::new`
+    ///
+    /// Creates a new `ClusterStatus` of the provided cluster.
+    ///
+    /// ## Parameters
+    /// - `cluster`, type `Cluster`: the gateway cluster
+    /// - `guilds`, type `GuildCounter`: the guilds of the shards of the cluster
+    pub fn new(cluster: Cluster, guilds: GuildCounter) -> Self {
+        Self {
+            cluster,
+            guilds
+        }
+    }
+}
+
+impl ShardStatusSource for ClusterStatus {
+    fn shard_statuses(&self) -> Vec<ShardStatus> {
+        self.cluster
+            .shards()
+            .filter_map(|shard| shard.info().ok())
+            .map(|info| ShardStatus {
+                id: info.id(),
+                state: format!("{stage:?}", stage = info.stage()).to_lowercase(),
+                latency_ms: info.latency().average().map(|latency| latency.as_millis()),
+                guilds: self.guilds.count(info.id())
+            })
+            .collect()
+    }
+}
+
+/// # Struct `BotCaches`
+///
+/// The sizes of the caches of the bot.
+pub struct BotCaches {
+    cache: InMemoryCache,
+    config_cache: GuildConfigCache
+}
+
+impl BotCaches {
+    /// # Constructor `BotCaches::new`
+    ///
+    /// Creates a new `BotCaches` of the provided caches.
+    ///
+    /// ## Parameters
+    /// - `cache`, type `InMemoryCache`: the in-memory cache
+    /// - `config_cache`, type `GuildConfigCache`: the guild configuration cache
+    pub fn new(cache: InMemoryCache, config_cache: GuildConfigCache) -> Self {
+        Self {
+            cache,
+            config_cache
+        }
+    }
+}
+
+impl CacheSizeSource for BotCaches {
+    fn cache_sizes(&self) -> Vec<(&'static str, usize)> {
+        let stats = self.cache.stats();
+
+        vec![
+            ("emojis", stats.emojis()),
+            ("guild_configs", self.config_cache.metrics().size),
+            ("guilds", stats.guilds()),
+            ("presences", stats.presences()),
+            ("roles", stats.roles()),
+            ("users", stats.users())
+        ]
+    }
+}
+
+/// # Struct `HealthState`
+///
+/// The state the health server reports on.
+pub struct HealthState {
+    shards: Arc<dyn ShardStatusSource + Send + Sync>,
+    caches: Arc<dyn CacheSizeSource + Send + Sync>,
+    pools: Option<PoolManager>
+}
+
+impl HealthState {
+    /// # Constructor `HealthState::new`
+    ///
+    /// Creates a new `HealthState`.
+    ///
+    /// ## Parameters
+    /// - `shards`, type `Arc<dyn ShardStatusSource + Send + Sync>`: the source of the statuses of
+    ///                                                              the shards
+    /// - `caches`, type `Arc<dyn CacheSizeSource + Send + Sync>`: the source of the sizes of the
+    ///                                                            caches
+    /// - `pools`, type `Option<PoolManager>`: the connection pools of the database, if the bot
+    ///                                        uses the postgres backend
+    pub fn new(
+        shards: Arc<dyn ShardStatusSource + Send + Sync>,
+        caches: Arc<dyn CacheSizeSource + Send + Sync>,
+        pools: Option<PoolManager>
+    ) -> Self {
+        Self {
+            shards,
+            caches,
+            pools
+        }
+    }
+
+    /// # Asynchronous Instance Method `HealthState::respond`
+    ///
+    /// Returns the response to a request to the health server.
+    ///
+    /// ## Parameters
+    /// - `method`, type `&Method`: the method of the request
+    /// - `path`, type `&str`: the path of the request
+    pub async fn respond(&self, method: &Method, path: &str) -> Response<Body> {
+        if method != Method::GET {
+            return text(StatusCode::METHOD_NOT_ALLOWED, "method not allowed");
+        }
+
+        match path {
+            "/healthz" => text(StatusCode::OK, "ok"),
+            "/readyz" => {
+                let statuses = self.shards.shard_statuses();
+
+                if statuses.is_empty() || statuses.iter().any(|status| status.state != CONNECTED) {
+                    return text(StatusCode::SERVICE_UNAVAILABLE, "not all shards are connected");
+                }
+
+                if let Some(pools) = &self.pools {
+                    if let Err(error) = pools.ping().await {
+                        return text(StatusCode::SERVICE_UNAVAILABLE, format!("the database is unreachable: {error:?}"));
+                    }
+                }
+
+                text(StatusCode::OK, "ready")
+            }
+            "/shards" => {
+                // unwrapping here is fine as serializing the statuses cannot fail
+                let json = serde_json::to_string(&self.shards.shard_statuses()).unwrap();
+
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(json))
+                    .unwrap()
+            }
+            "/metrics" => {
+                // the metrics describing the current state rather than what has happened are
+                // updated when they are scraped
+                for status in self.shards.shard_statuses() {
+                    hartex_metrics::gateway::set_shard_status(
+                        status.id,
+                        status.latency_ms.map(|latency| Duration::from_millis(latency as u64)),
+                        status.guilds
+                    );
+                }
+
+                for (resource, size) in self.caches.cache_sizes() {
+                    hartex_metrics::cache::set_size(resource, size);
+                }
+
+                Response::builder()
+                    .status(StatusCode::OK)
+                    .header(header::CONTENT_TYPE, hartex_metrics::CONTENT_TYPE)
+                    .body(Body::from(hartex_metrics::gather()))
+                    .unwrap()
+            }
+            _ => text(StatusCode::NOT_FOUND, "not found")
+        }
+    }
+}
+
+/// # Struct `HealthServer`
+///
+/// The running health server.
+pub struct HealthServer {
+    shutdown: oneshot::Sender<()>,
+    handle: JoinHandle<()>
+}
+
+impl HealthServer {
+    /// # Static Method `HealthServer::start`
+    ///
+    /// Starts the health server on the provided address.
+    ///
+    /// ## Parameters
+    /// - `address`, type `SocketAddr`: the address to listen on
+    /// - `state`, type `HealthState`: the state to report on
+    pub fn start(address: SocketAddr, state: HealthState) -> HarTexResult<Self> {
+        let state = Arc::new(state);
+        let make_service = make_service_fn(move |_| {
+            let state = state.clone();
+
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let state = state.clone();
+
+                    async move {
+                        Ok::<_, Infallible>(state.respond(request.method(), request.uri().path()).await)
+                    }
+                }))
+            }
+        });
+
+        let server = Server::try_bind(&address)
+            .map_err(|error| HarTexError::Custom {
+                message: format!("failed to bind health server to `{address}`: {error}")
+            })?
+            .serve(make_service);
+
+        let (shutdown, receiver) = oneshot::channel::<()>();
+        let handle = tokio::spawn(async move {
+            let graceful = server.with_graceful_shutdown(async {
+                // the server shuts down when the sender is either used or dropped
+                let _ = receiver.await;
+            });
+
+            if let Err(error) = graceful.await {
+                error!("health server failed: {error}");
+            }
+        });
+
+        info!("health server listening on `{address}`");
+
+        Ok(Self {
+            shutdown,
+            handle
+        })
+    }
+
+    /// # Asynchronous Instance Method `HealthServer::stop`
+    ///
+    /// Stops the health server, waiting for the requests being served to be responded to.
+    pub async fn stop(self) {
+        let _ = self.shutdown.send(());
+        let _ = self.handle.await;
+    }
+}
+
+/// # Function `text`
+///
+/// Returns a plain text response.
+///
+/// ## Parameters
+/// - `status`, type `StatusCode`: the status of the response
+/// - `body`, type `impl Into<String>`: the body of the response
+fn text(status: StatusCode, body: impl Into<String>) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Body::from(body.into()))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use hyper::{
+        body,
+        header,
+        Body,
+        Method,
+        Response,
+        StatusCode
+    };
+
+    use super::{
+        CacheSizeSource,
+        HealthState,
+        ShardStatus,
+        ShardStatusSource,
+        CONNECTED
+    };
+
+    /// # Struct `FakeShards`
+    ///
+    /// A source of fixed shard statuses, standing in for a gateway cluster.
+    struct FakeShards(Vec<ShardStatus>);
+
+    impl ShardStatusSource for FakeShards {
+        fn shard_statuses(&self) -> Vec<ShardStatus> {
+            self.0.clone()
+        }
+    }
+
+    /// # Struct `FakeCaches`
+    ///
+    /// A source of fixed cache sizes, standing in for the caches of the bot.
+    struct FakeCaches;
+
+    impl CacheSizeSource for FakeCaches {
+        fn cache_sizes(&self) -> Vec<(&'static str, usize)> {
+            vec![("guilds", 2)]
+        }
+    }
+
+    /// # Function `shard`
+    ///
+    /// Returns the status of a shard in the provided state.
+    fn shard(id: u64, state: &str) -> ShardStatus {
+        ShardStatus {
+            id,
+            state: state.to_string(),
+            latency_ms: Some(42),
+            guilds: 1
+        }
+    }
+
+    /// # Function `state`
+    ///
+    /// Returns the state of a health server with the provided shards and no database.
+    fn state(shards: Vec<ShardStatus>) -> HealthState {
+        HealthState::new(Arc::new(FakeShards(shards)), Arc::new(FakeCaches), None)
+    }
+
+    /// # Asynchronous Function `body_of`
+    ///
+    /// Returns the body of a response as a string.
+    async fn body_of(response: Response<Body>) -> String {
+        String::from_utf8(body::to_bytes(response.into_body()).await.unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn healthz_is_ok() {
+        let response = state(Vec::new()).respond(&Method::GET, "/healthz").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn readyz_is_unavailable_without_shards() {
+        let response = state(Vec::new()).respond(&Method::GET, "/readyz").await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn readyz_is_unavailable_while_a_shard_is_not_connected() {
+        let response = state(vec![shard(0, CONNECTED), shard(1, "resuming")])
+            .respond(&Method::GET, "/readyz")
+            .await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn readyz_is_ok_when_all_shards_are_connected() {
+        let response = state(vec![shard(0, CONNECTED), shard(1, CONNECTED)])
+            .respond(&Method::GET, "/readyz")
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn shards_are_reported_as_json() {
+        let response = state(vec![shard(0, CONNECTED), shard(1, "resuming")])
+            .respond(&Method::GET, "/shards")
+            .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+
+        let shards = serde_json::from_str::<serde_json::Value>(&body_of(response).await).unwrap();
+
+        assert_eq!(shards[0]["id"], 0);
+        assert_eq!(shards[0]["state"], CONNECTED);
+        assert_eq!(shards[1]["state"], "resuming");
+        assert_eq!(shards[1]["latency_ms"], 42);
+        assert_eq!(shards[1]["guilds"], 1);
+    }
+
+    #[tokio::test]
+    async fn metrics_are_reported_in_the_prometheus_text_format() {
+        hartex_metrics::gateway::record_event("GuildCreate");
+
+        let response = state(vec![shard(0, CONNECTED)]).respond(&Method::GET, "/metrics").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], hartex_metrics::CONTENT_TYPE);
+
+        let metrics = body_of(response).await;
+
+        assert!(metrics.contains("hartex_gateway_events_total{event=\"GuildCreate\"}"));
+        assert!(metrics.contains("hartex_shard_guilds{shard=\"0\"} 1"));
+        assert!(metrics.contains("hartex_cache_size{resource=\"guilds\"} 2"));
+    }
+
+    #[tokio::test]
+    async fn unknown_paths_are_not_found() {
+        let response = state(Vec::new()).respond(&Method::GET, "/unknown").await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn other_methods_are_not_allowed() {
+        let response = state(Vec::new()).respond(&Method::POST, "/healthz").await;
+
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+}
@@ -1,33 +1,37 @@
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::Instant
+};
 
 use hartex_core::{
-    discord::{
-        cache_inmemory::InMemoryCache,
-        gateway::Cluster,
-        http::Client,
-        model::application::interaction::Interaction
+    discord::model::{
+        application::{
+            callback::{
+                CallbackData,
+                InteractionResponse
+            },
+            interaction::Interaction
+        },
+        channel::message::MessageFlags
     },
     error::HarTexResult
 };
 
 use hartex_cmdsys::{
-    command::Command,
     context::{
         CommandContext,
         CommandContextInner
-    }
+    },
+    featuregate
 };
 
-use hartex_logging::Logger;
+use hartex_logging::error;
 
-use hartex_plugins::{
-    global::{
-        about::About,
-        ping::Ping,
-        source::Source,
-        team::Team,
-    },
-    information::userinfo::Userinfo
+use hartex_metrics::http::RecordHttp;
+
+use crate::{
+    commands,
+    services::BotServices
 };
 
 /// # Asynchronous Function `handle_interaction`
@@ -36,97 +40,82 @@ use hartex_plugins::{
 ///
 /// ## Parameters
 /// - `interaction`, type `Interaction`: the interaction
-/// - `cache`, type `InMemoryCache`: the in-memory cache
-/// - `http`, type `Client`: the Twilight HTTP client
-/// - `cluster`: the gateway cluster
-pub async fn handle_interaction(
-    interaction: Interaction,
-    cache: InMemoryCache,
-    http: Client,
-    cluster: Cluster
-) -> HarTexResult<()> {
-    match {
-        match interaction.clone() {
-            Interaction::ApplicationCommand(command) => {
-                match &*command.data.name {
-                    // Global Plugin
-                    "about" => {
-                        About.execute(
-                            CommandContext {
-                                inner: Arc::new(CommandContextInner {
-                                    http,
-                                    cluster,
-                                    interaction
-                                })
-                            },
-                            cache
-                        ).await
-                    }
-                    "ping" => {
-                        Ping.execute(
-                            CommandContext {
-                                inner: Arc::new(CommandContextInner {
-                                    http,
-                                    cluster,
-                                    interaction
-                                })
-                            },
-                            cache
-                        ).await
-                    }
-                    "source" => {
-                        Source.execute(
-                            CommandContext {
-                                inner: Arc::new(CommandContextInner {
-                                    http,
-                                    cluster,
-                                    interaction
-                                })
-                            },
-                            cache
-                        ).await
-                    },
-                    "team" => {
-                        Team.execute(
-                            CommandContext {
-                                inner: Arc::new(CommandContextInner {
-                                    http,
-                                    cluster,
-                                    interaction
-                                })
-                            },
-                            cache
-                        ).await
-                    }
+/// - `services`, type `BotServices`: the services to handle the interaction with
+pub async fn handle_interaction(interaction: Interaction, services: BotServices) -> HarTexResult<()> {
+    let BotServices {
+        cache,
+        http,
+        cluster,
+        repository,
+        config_cache,
+        emitter,
+        standby,
+        config
+    } = services;
+
+    let command = match &interaction {
+        Interaction::ApplicationCommand(command) => command,
+        _ => return Ok(())
+    };
+
+    let handler = match commands::global_commands()
+        .into_iter()
+        .find(|handler| handler.name() == command.data.name) {
+        Some(handler) => handler,
+        None => return Ok(())
+    };
 
-                    // Information Plugin
-                    "userinfo" => {
-                        Userinfo.execute(
-                            CommandContext {
-                                inner: Arc::new(CommandContextInner {
-                                    http,
-                                    cluster,
-                                    interaction
-                                })
-                            },
-                            cache
-                        ).await
-                    }
-                    _ => Ok(())
+    // commands requiring a nightly feature are not executed in guilds that have not opted in to it
+    let required_feature = handler.required_nightly_feature();
+    if !featuregate::is_enabled(&config_cache, command.guild_id, required_feature).await {
+        // unwrapping here is fine as the command does require a nightly feature if it is disabled
+        let feature = required_feature.unwrap();
+
+        http.interaction_callback(
+            command.id,
+            &command.token,
+            &InteractionResponse::ChannelMessageWithSource(
+                CallbackData {
+                    allowed_mentions: None,
+                    components: None,
+                    content: Some(format!(":x: This command requires the `{feature}` nightly feature, which this guild has not opted in to.")),
+                    embeds: vec![],
+                    flags: Some(MessageFlags::EPHEMERAL),
+                    tts: None
                 }
-            }
-            _ => Ok(())
-        }
-    } {
+            )
+        )
+            .exec()
+            .recorded("interaction_callback")
+            .await?;
+
+        return Ok(());
+    }
+
+    let name = handler.name();
+    let start = Instant::now();
+    let result = handler.execute(
+        CommandContext {
+            inner: Arc::new(CommandContextInner {
+                http,
+                cluster,
+                interaction,
+                repository,
+                config_cache,
+                emitter,
+                standby,
+                global_administrator_id: config.global_administrator_id
+            })
+        },
+        cache
+    ).await;
+
+    hartex_metrics::commands::record(&name, start.elapsed(), result.is_ok());
+
+    match result {
         Ok(_) => (),
         Err(error) => {
-            Logger::error(
-                format!("failed to handle interaction due to an error: {error:?}"),
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
+            error!(command = name; "failed to handle interaction due to an error: {error:?}");
         }
     }
 
@@ -5,10 +5,7 @@
 
 #![feature(format_args_capture)]
 
-use std::{
-    env,
-    process
-};
+use std::sync::Arc;
 
 use futures_util::future::Either;
 
@@ -17,118 +14,119 @@ use tokio_stream::StreamExt;
 use hartex_cmdsys::framework::CommandFramework;
 
 use hartex_core::{
-    ctrlc,
     discord::{
-        cache_inmemory::{
-            InMemoryCache,
-            ResourceType
-        },
-        gateway::{
-            cluster::{
-                Cluster,
-                ShardScheme
-            },
-            EventTypeFlags,
-            Intents
-        },
+        cache_inmemory::InMemoryCache,
+        gateway::cluster::Cluster,
         http::Client,
-        model::id::ApplicationId
+        standby::Standby
+    },
+    error::{
+        HarTexError,
+        HarTexResult
     },
-    error::HarTexResult,
     events::EventType
 };
 
-use hartex_eventsys::emitter::EventEmitter;
+use hartex_dbmani::{
+    confcache::{
+        self,
+        GuildConfigCache
+    },
+    confnotify,
+    migrations,
+    pool::PoolManager,
+    repository::{
+        inmemory::InMemoryRepository,
+        postgres::PostgresRepository,
+        SharedRepository
+    }
+};
+
+use hartex_eventsys::{
+    emitter::EventEmitter,
+    events::HarTexEvent
+};
+
+use hartex_logging::{
+    sink::{
+        FileSink,
+        LogSink,
+        StderrSink,
+        StdoutSink
+    },
+    error,
+    info,
+    verbose,
+    warn,
+    Logger
+};
 
-use hartex_logging::Logger;
+use hartex_model::payload::ConfigurationReloaded;
+
+use crate::{
+    config::{
+        BotConfig,
+        ConfigOverrides,
+        DatabaseConfig,
+        LogSinkConfig
+    },
+    health::{
+        BotCaches,
+        ClusterStatus,
+        GuildCounter,
+        HealthServer,
+        HealthState
+    },
+    requirements::GatewayRequirements,
+    services::BotServices,
+    shutdown::TaskTracker,
+    startup::Role
+};
 
 pub mod commands;
+pub mod config;
+pub mod dryrun;
 pub mod events;
 pub mod handler;
+pub mod health;
 pub mod interactions;
+pub mod maintenance;
+pub mod requirements;
+pub mod services;
+pub mod sessions;
+pub mod shutdown;
+pub mod startup;
 
 /// # Asynchronous Function `hartex_main`
 ///
 /// This is the main entry point of HarTex Discord Bot.
-pub async fn hartex_main() -> HarTexResult<()> {
-    // loads the .env file to obtain environment variables
-    dotenv::dotenv().ok();
-
-    Logger::verbose(
-        "loaded environment variables",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
-    );
-
-    // obtains the token from the environment variables
-    let token = match env::var("HARTEX_TOKEN") {
-        Ok(token) => token,
-        Err(var_error) => {
-            Logger::error(
-                format!("could not retrieve the bot token from the environment due to an error: {var_error}"),
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
-
-            process::exit(-1)
-        }
-    };
-
-    let application_id = match env::var("APPLICATION_ID") {
-        Ok(id) => id,
-        Err(var_error) => {
-            Logger::error(
-                format!("could not retrieve the application id from the environment due to an error: {var_error}"),
-                Some(module_path!()),
-                file!(),
-                line!(),
-                column!()
-            );
-
-            process::exit(-1)
-        }
-    };
-
-    Logger::verbose(
-        "successfully retrieved bot token",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
-    );
-
-    let shard_scheme = ShardScheme::Auto;
-
-    Logger::verbose(
-        "building bot cluster",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
-    );
-    Logger::verbose(
-        "registering gateway intents [all]",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
+///
+/// ## Parameters
+/// - `overrides`, type `ConfigOverrides`: the configuration values specified on the command line
+pub async fn hartex_main(overrides: ConfigOverrides) -> HarTexResult<()> {
+    let config = Arc::new(load_config(&overrides)?);
+    let requirements = GatewayRequirements::resolve(&config, &commands::global_commands());
+
+    verbose!("building bot cluster; running shards [{scheme:?}]", scheme = config.shard_scheme);
+    verbose!(
+        "registering gateway intents [{intents:?}] and event types [{event_types:?}]",
+        intents = requirements.intents,
+        event_types = requirements.event_types
     );
 
-    let intents = Intents::all();
+    let http = build_http_client(&config);
 
-    let http = Client::builder()
-        .application_id(ApplicationId::from(application_id.parse::<u64>().unwrap()))
-        .token(token.clone())
-        .build();
+    // resuming the sessions saved when the bot last shut down, if any
+    let resume_sessions = sessions::load(&config.sessions_file);
+    if !resume_sessions.is_empty() {
+        verbose!("resuming {count} saved gateway sessions", count = resume_sessions.len());
+    }
 
-    let (cluster, events) = Cluster::builder(token, intents)
-        .event_types(EventTypeFlags::all())
+    let (cluster, events) = Cluster::builder(config.token.clone(), requirements.intents)
+        .event_types(requirements.event_types)
         .http_client(http.clone())
-        .shard_scheme(shard_scheme)
+        .shard_scheme(config.shard_scheme.clone())
+        .resume_sessions(resume_sessions)
         .build()
         .await?;
 
@@ -138,21 +136,9 @@ pub async fn hartex_main() -> HarTexResult<()> {
         cluster_spawn.up().await;
     });
 
-    Logger::verbose(
-        "building http client",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
-    );
+    verbose!("building http client");
 
-    Logger::verbose(
-        "initializing command framework",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
-    );
+    verbose!("initializing command framework");
     let framework = CommandFramework::default();
 
     let listeners = framework.clone().listeners();
@@ -160,62 +146,250 @@ pub async fn hartex_main() -> HarTexResult<()> {
 
     let framework_events = framework.events();
 
-    Logger::verbose(
-        "building in-memory cache",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
-    );
+    verbose!("building in-memory cache; caching [{resource_types:?}]", resource_types = requirements.resource_types);
 
     let cache = InMemoryCache::builder()
-        .resource_types(ResourceType::all())
+        .resource_types(requirements.resource_types)
         .build();
 
-    Logger::verbose(
-        "registering ctrl-c handler",
-        Some(module_path!()),
-        file!(),
-        line!(),
-        column!()
-    );
+    let (repository, pools) = connect_repository(&config).await?;
+
+    let config_cache = GuildConfigCache::new(repository.clone(), confcache::DEFAULT_TTL);
+    let standby = Arc::new(Standby::new());
+
+    let guild_counter = GuildCounter::default();
+    let health_server = match config.health_address {
+        Some(address) => Some(HealthServer::start(
+            address,
+            HealthState::new(
+                Arc::new(ClusterStatus::new(cluster.clone(), guild_counter.clone())),
+                Arc::new(BotCaches::new(cache.clone(), config_cache.clone())),
+                pools.clone()
+            )
+        )?),
+        None => None
+    };
+
+    let tasks = TaskTracker::new();
+
+    // when the shards are split across several processes, the tasks concerning the bot as a whole
+    // are only performed by one of them
+    let role = Role::elect(&config).await?;
+    if role.leader {
+        let global_http = http.clone();
+        let global_config_cache = config_cache.clone();
 
-    ctrlc::set_handler(|| {
-        Logger::warn(
-            "ctrl-c signal received; terminating process",
-            Some(module_path!()),
-            file!(),
-            line!(),
-            column!()
-        );
+        tasks.spawn(async move {
+            if let Err(error) = startup::perform_global_tasks(global_http, global_config_cache).await {
+                error!("failed to perform the global startup tasks: {error:?}");
+            }
+        });
+    }
+
+    let config_listener = match &config.database {
+        DatabaseConfig::InMemory => None,
+        DatabaseConfig::Postgres { guildconfig_url, .. } => {
+            verbose!("listening for guild configuration changes");
+
+            let (mut reloads, config_listener) = confnotify::listen(guildconfig_url.clone(), config_cache.clone());
+            let reloads_emitter = emitter.clone();
 
-        process::exit(0)
-    })?;
+            // the stream of reloaded guilds ends once the listener has been stopped
+            tasks.spawn(async move {
+                while let Some(guild_id) = reloads.next().await {
+                    reloads_emitter.emit(HarTexEvent::ConfigurationReloaded(Box::new(ConfigurationReloaded {
+                        guild_id
+                    })));
+                }
+            });
+
+            Some(config_listener)
+        }
+    };
+
+    let services = BotServices {
+        cache: cache.clone(),
+        http: http.clone(),
+        cluster: cluster.clone(),
+        repository,
+        config_cache,
+        emitter,
+        standby: standby.clone(),
+        config: config.clone()
+    };
+
+    let shutdown = shutdown::signal();
+    tokio::pin!(shutdown);
 
     let mut events = events.map(Either::Left).merge(framework_events.map(Either::Right));
 
-    while let Some(event) = events.next().await {
+    loop {
+        let event = tokio::select! {
+            event = events.next() => match event {
+                Some(event) => event,
+                None => break
+            },
+            _ = &mut shutdown => break
+        };
+
         match event {
-            Either::Left((_, twilight)) => {
-                tokio::spawn(events::handle_event(
-                    (EventType::Twilight, Some(twilight), None),
-                    http.clone(),
-                    emitter.clone(),
-                    cache.clone(),
-                    cluster.clone()
-                ));
+            Either::Left((shard_id, twilight)) => {
+                hartex_metrics::gateway::record_event(&format!("{kind:?}", kind = twilight.kind()));
+                guild_counter.update(shard_id, &twilight);
+                cache.update(&twilight);
+                standby.process(&twilight);
+
+                tasks.spawn(events::handle_event((EventType::Twilight, Some(twilight), None), services.clone()));
             }
             Either::Right(custom) => {
-                tokio::spawn(events::handle_event(
-                    (EventType::Custom, None, Some(custom)),
-                    http.clone(),
-                    emitter.clone(),
-                    cache.clone(),
-                    cluster.clone()
-                ));
+                tasks.spawn(events::handle_event((EventType::Custom, None, Some(custom)), services.clone()));
             }
         }
     }
 
+    // no new events are accepted from here on; the in-flight ones may still use the cluster and
+    // the database, so they are waited for before those are shut down
+    info!("shutting down; waiting for in-flight events to be handled");
+
+    if let Some(config_listener) = config_listener {
+        config_listener.stop().await;
+    }
+
+    if !tasks.wait(shutdown::SHUTDOWN_TIMEOUT).await {
+        warn!("in-flight events have not been handled within {timeout:?}; abandoning them", timeout = shutdown::SHUTDOWN_TIMEOUT);
+    }
+
+    let resumable_sessions = cluster.down_resumable();
+
+    info!("closed {count} shards with resumable sessions; saving them", count = resumable_sessions.len());
+
+    sessions::save(&config.sessions_file, resumable_sessions);
+
+    if let Some(leadership) = role.leadership {
+        leadership.release().await;
+    }
+
+    if let Some(health_server) = health_server {
+        health_server.stop().await;
+    }
+
+    // the configuration listener holds its own connection and has been stopped above, so closing
+    // the pools only waits for the connections used by abandoned tasks, if any
+    if let Some(pools) = pools {
+        if tokio::time::timeout(shutdown::SHUTDOWN_TIMEOUT, pools.close()).await.is_err() {
+            warn!("the database connection pools have not closed within {timeout:?}; abandoning them", timeout = shutdown::SHUTDOWN_TIMEOUT);
+        }
+    }
+
+    info!("shut down");
+    Logger::flush();
+
+    Ok(())
+}
+
+/// # Asynchronous Function `hartex_migrate`
+///
+/// Applies the pending database migrations without starting the bot.
+///
+/// ## Parameters
+/// - `overrides`, type `ConfigOverrides`: the configuration values specified on the command line
+pub async fn hartex_migrate(overrides: ConfigOverrides) -> HarTexResult<()> {
+    let config = load_config(&overrides)?;
+
+    // the pending migrations are applied when the repository is connected
+    match connect_repository(&config).await? {
+        (_, Some(pools)) => {
+            info!("database migrations applied");
+
+            pools.close().await;
+        }
+        (_, None) => warn!("the in-memory database backend has no migrations to apply")
+    }
+
+    Logger::flush();
+
     Ok(())
 }
+
+/// # Function `build_http_client`
+///
+/// Builds the Twilight HTTP client with the token and the application id of the bot.
+///
+/// ## Parameters
+/// - `config`, type `&BotConfig`: the bot-level configuration
+pub(crate) fn build_http_client(config: &BotConfig) -> Client {
+    Client::builder()
+        .application_id(config.application_id)
+        .token(config.token.clone())
+        .build()
+}
+
+/// # Asynchronous Function `connect_repository`
+///
+/// Creates the repository of the configured database backend; for the postgres backend, the
+/// connection pools are created and the pending migrations applied, and the pools are returned
+/// along with the repository.
+///
+/// ## Parameters
+/// - `config`, type `&BotConfig`: the bot-level configuration
+pub(crate) async fn connect_repository(config: &BotConfig) -> HarTexResult<(SharedRepository, Option<PoolManager>)> {
+    // the in-memory backend is intended for local development only; all data stored in it is lost
+    // when the process exits
+    let (repository, pools): (SharedRepository, Option<PoolManager>) = match &config.database {
+        DatabaseConfig::InMemory => {
+            warn!("using the in-memory database backend; data will not be persisted");
+
+            (Arc::new(InMemoryRepository::new()), None)
+        }
+        DatabaseConfig::Postgres { guildconfig_url, whitelist_url, pool } => {
+            verbose!("using the postgres database backend; creating connection pools");
+
+            let pools = PoolManager::connect(guildconfig_url, whitelist_url, pool.clone()).await?;
+            migrations::run_migrations(&pools).await?;
+
+            (Arc::new(PostgresRepository::new(pools.clone())), Some(pools))
+        }
+    };
+
+    Ok((repository, pools))
+}
+
+/// # Function `load_config`
+///
+/// Loads the `.env` file and the bot-level configuration, and applies the configured log filter and sinks.
+///
+/// ## Parameters
+/// - `overrides`, type `&ConfigOverrides`: the configuration values specified on the command line
+pub(crate) fn load_config(overrides: &ConfigOverrides) -> HarTexResult<BotConfig> {
+    // loads the .env file to obtain environment variables
+    dotenv::dotenv().ok();
+
+    let config = match BotConfig::load(overrides) {
+        Ok(config) => config,
+        Err(error) => {
+            error!("failed to load the bot configuration: {error:?}");
+
+            return Err(error);
+        }
+    };
+
+    let sinks = config.log_sinks
+        .iter()
+        .map(|sink| -> HarTexResult<Box<dyn LogSink + Send + Sync>> {
+            Ok(match sink {
+                LogSinkConfig::Stdout => Box::new(StdoutSink::new(config.log_format)),
+                LogSinkConfig::Stderr => Box::new(StderrSink::new(config.log_format)),
+                LogSinkConfig::File(path) => Box::new(FileSink::open(path, config.log_format).map_err(|error| HarTexError::Custom {
+                    message: format!("failed to open the log file `{}`: {error}", path.display())
+                })?)
+            })
+        })
+        .collect::<HarTexResult<Vec<_>>>()?;
+
+    Logger::set_sinks(sinks);
+    Logger::set_filter(config.log_filter.clone());
+    Logger::set_process(config.process_name.clone());
+    verbose!("loaded bot configuration");
+
+    Ok(config)
+}
@@ -0,0 +1,310 @@
+//! # The `maintenance` Module
+//!
+//! This module implements the maintenance tasks of the bot that can be performed from the command
+//! line without a running gateway connection, such as synchronizing the global commands and
+//! managing the guild whitelist.
+
+use std::{
+    fs,
+    path::Path,
+    sync::Arc
+};
+
+use serde::de::DeserializeSeed;
+
+use hartex_cmdsys::framework::CommandFramework;
+
+use hartex_core::{
+    discord::{
+        cache_inmemory::InMemoryCache,
+        gateway::{
+            cluster::Cluster,
+            Event
+        },
+        model::{
+            gateway::event::{
+                GatewayEvent,
+                GatewayEventDeserializer
+            },
+            id::GuildId
+        },
+        standby::Standby
+    },
+    error::{
+        HarTexError,
+        HarTexResult
+    },
+    events::EventType
+};
+
+use hartex_dbmani::{
+    confcache::{
+        self,
+        GuildConfigCache
+    },
+    repository::SharedRepository
+};
+
+use hartex_eventsys::emitter::EventEmitter;
+
+use hartex_logging::{
+    error,
+    info,
+    verbose,
+    warn
+};
+
+use crate::{
+    commands,
+    config::ConfigOverrides,
+    dryrun::{
+        ReadOnlyRepository,
+        RecordingServer
+    },
+    events,
+    requirements::GatewayRequirements,
+    services::BotServices
+};
+
+/// # Enum `WhitelistAction`
+///
+/// An operation on the guild whitelist.
+pub enum WhitelistAction {
+    /// # Enum Variant `WhitelistAction::List`
+    ///
+    /// Lists the whitelisted guilds.
+    List,
+
+    /// # Enum Variant `WhitelistAction::Add`
+    ///
+    /// Whitelists a guild, or reactivates its whitelist.
+    Add {
+        guild_id: GuildId,
+        name: String
+    },
+
+    /// # Enum Variant `WhitelistAction::Remove`
+    ///
+    /// Removes the whitelist of a guild.
+    Remove {
+        guild_id: GuildId
+    }
+}
+
+/// # Asynchronous Function `hartex_sync_commands`
+///
+/// Registers the global commands that have not been registered yet.
+///
+/// ## Parameters
+/// - `overrides`, type `ConfigOverrides`: the configuration values specified on the command line
+/// - `dry_run`, type `bool`: whether to only log the commands that would be registered
+pub async fn hartex_sync_commands(overrides: ConfigOverrides, dry_run: bool) -> HarTexResult<()> {
+    let config = crate::load_config(&overrides)?;
+    let http = crate::build_http_client(&config);
+
+    commands::register_global_commands(commands::global_commands(), http, dry_run).await?;
+
+    info!("{}", if dry_run { "global commands checked; no changes have been made" } else { "global commands synchronized" });
+
+    Ok(())
+}
+
+/// # Function `hartex_validate_config`
+///
+/// Validates a guild TOML configuration file, as it would be validated when it is uploaded; the
+/// applied migrations and the problems found are logged.
+///
+/// ## Parameters
+/// - `path`, type `&Path`: the path of the configuration file
+pub fn hartex_validate_config(path: &Path) -> HarTexResult<()> {
+    let source = fs::read_to_string(path).map_err(|error| HarTexError::Custom {
+        message: format!("failed to read configuration file `{path}`: {error}", path = path.display())
+    })?;
+
+    hartex_conftoml::from_string_migrated(source)?;
+
+    info!("configuration file `{path}` is valid", path = path.display());
+
+    Ok(())
+}
+
+/// # Asynchronous Function `hartex_whitelist`
+///
+/// Performs an operation on the guild whitelist.
+///
+/// ## Parameters
+/// - `overrides`, type `ConfigOverrides`: the configuration values specified on the command line
+/// - `action`, type `WhitelistAction`: the operation to perform
+pub async fn hartex_whitelist(overrides: ConfigOverrides, action: WhitelistAction) -> HarTexResult<()> {
+    let config = crate::load_config(&overrides)?;
+    let (repository, pools) = crate::connect_repository(&config).await?;
+
+    match action {
+        WhitelistAction::List => {
+            let guilds = repository.whitelisted_guilds().await?;
+
+            if guilds.is_empty() {
+                println!("there are no whitelisted guilds");
+            }
+
+            for guild in guilds {
+                let mut line = format!("{id}\t{name}\t{status}", id = guild.GuildId, name = guild.GuildName, status = guild.Status);
+
+                if let Some(reason) = &guild.Reason {
+                    line.push_str(&format!("\t{reason}"));
+                }
+
+                println!("{line}");
+            }
+        }
+        WhitelistAction::Add { guild_id, name } => {
+            // the whitelist records who added a guild; from the command line, that is the global
+            // administrator
+            let added_by = config.global_administrator_id.ok_or_else(|| HarTexError::Custom {
+                message: String::from("whitelisting a guild requires the global administrator id to be configured")
+            })?;
+
+            repository.add_whitelisted_guild(guild_id, name, added_by).await?;
+
+            info!("guild {guild_id} is now whitelisted");
+        }
+        WhitelistAction::Remove { guild_id } => {
+            if repository.remove_whitelisted_guild(guild_id).await? {
+                info!("the whitelist of guild {guild_id} has been removed");
+            }
+            else {
+                warn!("guild {guild_id} is not whitelisted");
+            }
+        }
+    }
+
+    if let Some(pools) = pools {
+        pools.close().await;
+    }
+
+    Ok(())
+}
+
+/// # Asynchronous Function `hartex_replay`
+///
+/// Replays recorded gateway events through the event handlers, one after another, without
+/// connecting to the gateway; used to reproduce problems. The events are read from a file
+/// containing a raw gateway payload per line; payloads other than dispatches are skipped.
+///
+/// Unless `live` is set, the replay has no side effects: the requests of the handlers to the
+/// Discord API are logged and refused by a local server instead of being sent, and the writes to
+/// the database are logged instead of being performed. Otherwise, the handlers run as they would
+/// for events received from the gateway.
+///
+/// ## Parameters
+/// - `overrides`, type `ConfigOverrides`: the configuration values specified on the command line
+/// - `path`, type `&Path`: the path of the file containing the events
+/// - `live`, type `bool`: whether to send the requests to the Discord API and write to the database
+pub async fn hartex_replay(overrides: ConfigOverrides, path: &Path, live: bool) -> HarTexResult<()> {
+    let config = Arc::new(crate::load_config(&overrides)?);
+    let source = fs::read_to_string(path).map_err(|error| HarTexError::Custom {
+        message: format!("failed to read events file `{path}`: {error}", path = path.display())
+    })?;
+
+    let http = crate::build_http_client(&config);
+    let requirements = GatewayRequirements::resolve(&config, &commands::global_commands());
+
+    // the cluster is required by the handlers, but is never brought up; building it only retrieves
+    // the gateway information from the Discord API
+    let (cluster, _) = Cluster::builder(config.token.clone(), requirements.intents)
+        .http_client(http.clone())
+        .shard_scheme(config.shard_scheme.clone())
+        .build()
+        .await?;
+
+    let framework = CommandFramework::default();
+    let emitter = EventEmitter::new(framework.listeners());
+    let cache = InMemoryCache::builder()
+        .resource_types(requirements.resource_types)
+        .build();
+    let (repository, pools) = crate::connect_repository(&config).await?;
+    let standby = Arc::new(Standby::new());
+
+    let (http, repository, recording_server): (_, SharedRepository, _) = if live {
+        warn!("replaying live; the requests are sent to the Discord API and the database is written to");
+
+        (http, repository, None)
+    }
+    else {
+        let recording_server = RecordingServer::start()?;
+
+        (recording_server.http_client(&config), Arc::new(ReadOnlyRepository::new(repository)), Some(recording_server))
+    };
+
+    let services = BotServices {
+        cache: cache.clone(),
+        http,
+        cluster,
+        config_cache: GuildConfigCache::new(repository.clone(), confcache::DEFAULT_TTL),
+        repository,
+        emitter,
+        standby: standby.clone(),
+        config
+    };
+
+    let mut replayed = 0;
+
+    for (index, line) in source.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let line_number = index + 1;
+
+        let twilight = match parse_dispatch(line) {
+            Ok(Some(event)) => event,
+            Ok(None) => continue,
+            Err(error) => {
+                warn!("skipping line {line_number}: {error:?}");
+
+                continue;
+            }
+        };
+
+        verbose!("replaying {kind:?} event from line {line_number}", kind = twilight.kind());
+
+        cache.update(&twilight);
+        standby.process(&twilight);
+
+        if let Err(error) = events::handle_event((EventType::Twilight, Some(twilight), None), services.clone()).await {
+            error!("failed to handle event from line {line_number}: {error:?}");
+        }
+
+        replayed += 1;
+    }
+
+    if let Some(pools) = pools {
+        pools.close().await;
+    }
+
+    info!("replayed {replayed} events");
+
+    if let Some(recording_server) = recording_server {
+        info!("{count} requests were not sent to Discord", count = recording_server.stop().await.len());
+    }
+
+    Ok(())
+}
+
+/// # Function `parse_dispatch`
+///
+/// Parses a raw gateway payload; returns `None` if the payload is not a dispatch.
+///
+/// ## Parameters
+/// - `json`, type `&str`: the raw gateway payload
+fn parse_dispatch(json: &str) -> HarTexResult<Option<Event>> {
+    let deserializer = GatewayEventDeserializer::from_json(json).ok_or_else(|| HarTexError::Custom {
+        message: String::from("not a gateway payload")
+    })?;
+
+    let mut json_deserializer = serde_json::Deserializer::from_str(json);
+    let event = deserializer.deserialize(&mut json_deserializer).map_err(|error| HarTexError::Custom {
+        message: format!("invalid gateway payload: {error}")
+    })?;
+
+    Ok(match event {
+        GatewayEvent::Dispatch(_, dispatch) => Some(Event::from(dispatch)),
+        _ => None
+    })
+}
@@ -0,0 +1,102 @@
+//! # The `requirements` Module
+//!
+//! This module derives the gateway intents, the gateway event types and the cache resource types
+//! the bot needs from the events it handles and the commands it registers, so that it receives and
+//! caches no more than it uses.
+
+use hartex_cmdsys::command::Command;
+
+use hartex_core::discord::{
+    cache_inmemory::ResourceType,
+    gateway::{
+        EventTypeFlags,
+        Intents
+    }
+};
+
+use hartex_logging::warn;
+
+use crate::config::{
+    BotConfig,
+    Selection
+};
+
+/// # Struct `GatewayRequirements`
+///
+/// The gateway intents, gateway event types and cache resource types the bot runs with.
+#[derive(Clone, Copy, Debug)]
+pub struct GatewayRequirements {
+    pub intents: Intents,
+    pub event_types: EventTypeFlags,
+    pub resource_types: ResourceType
+}
+
+impl GatewayRequirements {
+    /// # Static Method `GatewayRequirements::derive`
+    ///
+    /// Derives the minimal requirements of the bot: those of the event handlers of the bot itself,
+    /// combined with those declared by the commands.
+    ///
+    /// ## Parameters
+    /// - `commands`, type `&[Box<dyn Command + Send + Sync>]`: the commands of the bot
+    pub fn derive(commands: &[Box<dyn Command + Send + Sync>]) -> Self {
+        // the events handled in `events::handle_event`; the guild whitelist is checked when a guild
+        // is created, which requires the `GUILDS` intent, as do the created threads that are joined
+        // in guilds that have opted in to the `threads` nightly feature; the guilds of the shards
+        // are counted from the `GuildCreate` and `GuildDelete` events for the health server
+        let base = Self {
+            intents: Intents::GUILDS,
+            event_types: EventTypeFlags::GUILD_CREATE
+                | EventTypeFlags::GUILD_DELETE
+                | EventTypeFlags::INTERACTION_CREATE
+                | EventTypeFlags::READY
+                | EventTypeFlags::SHARD_IDENTIFYING
+                | EventTypeFlags::THREAD_CREATE,
+            resource_types: ResourceType::empty()
+        };
+
+        commands.iter().fold(base, |requirements, command| Self {
+            intents: requirements.intents | command.required_intents(),
+            event_types: requirements.event_types | command.required_event_types(),
+            resource_types: requirements.resource_types | command.required_resource_types()
+        })
+    }
+
+    /// # Static Method `GatewayRequirements::resolve`
+    ///
+    /// Resolves the requirements the bot runs with: the configured ones where they are configured,
+    /// and the derived ones otherwise. Configured intents that lack some of the derived ones are
+    /// warned about, as the commands needing them will not work properly.
+    ///
+    /// ## Parameters
+    /// - `config`, type `&BotConfig`: the bot-level configuration
+    /// - `commands`, type `&[Box<dyn Command + Send + Sync>]`: the commands of the bot
+    pub fn resolve(config: &BotConfig, commands: &[Box<dyn Command + Send + Sync>]) -> Self {
+        let derived = Self::derive(commands);
+
+        let intents = match config.intents {
+            Some(intents) => {
+                let missing = derived.intents - intents;
+
+                if !missing.is_empty() {
+                    warn!("the configured gateway intents lack intents that some commands need: {missing:?}");
+                }
+
+                intents
+            }
+            None => derived.intents
+        };
+
+        Self {
+            intents,
+            event_types: match config.event_types {
+                Selection::Derived => derived.event_types,
+                Selection::All => EventTypeFlags::all()
+            },
+            resource_types: match config.resource_types {
+                Selection::Derived => derived.resource_types,
+                Selection::All => ResourceType::all()
+            }
+        }
+    }
+}
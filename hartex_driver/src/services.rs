@@ -0,0 +1,46 @@
+//! # The `services` Module
+//!
+//! This module defines the services of the bot that the events are handled with, which are created
+//! once when the bot starts up and shared by all the event handlers.
+
+use std::sync::Arc;
+
+use hartex_core::discord::{
+    cache_inmemory::InMemoryCache,
+    gateway::Cluster,
+    http::Client,
+    standby::Standby
+};
+
+use hartex_dbmani::{
+    confcache::GuildConfigCache,
+    repository::SharedRepository
+};
+
+use hartex_eventsys::emitter::EventEmitter;
+
+use crate::config::BotConfig;
+
+/// # Struct `BotServices`
+///
+/// The services of the bot that the events are handled with. Cloning this structure is cheap, and
+/// all clones share the same services.
+#[derive(Clone)]
+pub struct BotServices {
+    /// The in-memory cache.
+    pub cache: InMemoryCache,
+    /// The Twilight HTTP client.
+    pub http: Client,
+    /// The gateway cluster.
+    pub cluster: Cluster,
+    /// The repository of the configured database backend.
+    pub repository: SharedRepository,
+    /// The guild configuration cache.
+    pub config_cache: GuildConfigCache,
+    /// The event emitter.
+    pub emitter: EventEmitter,
+    /// The standby.
+    pub standby: Arc<Standby>,
+    /// The bot-level configuration.
+    pub config: Arc<BotConfig>
+}
@@ -0,0 +1,130 @@
+//! # The `sessions` Module
+//!
+//! This module persists the gateway sessions of the shards across restarts. The sessions are
+//! saved to a file when the bot shuts down, and loaded when it starts up again so that the shards
+//! resume their sessions instead of identifying again, which is faster and does not count towards
+//! the identify limit.
+//!
+//! A session that can no longer be resumed is not a problem; the gateway invalidates it and the
+//! shard identifies again.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::ErrorKind,
+    path::Path
+};
+
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+use hartex_core::{
+    discord::gateway::shard::ResumeSession,
+    time::Utc
+};
+
+use hartex_logging::{
+    verbose,
+    warn
+};
+
+/// # Constant `MAX_SESSION_AGE_SECS`
+///
+/// The age after which saved sessions are not resumed anymore, as the gateway would most likely
+/// have invalidated them.
+const MAX_SESSION_AGE_SECS: i64 = 5 * 60;
+
+/// # Struct `SavedSessions`
+///
+/// The sessions of the shards, as they are saved to the file.
+#[derive(Deserialize, Serialize)]
+struct SavedSessions {
+    // the unix timestamp of when the sessions were saved
+    saved_at: i64,
+    sessions: HashMap<u64, SavedSession>
+}
+
+/// # Struct `SavedSession`
+///
+/// The session of a shard, as it is saved to the file.
+#[derive(Deserialize, Serialize)]
+struct SavedSession {
+    session_id: String,
+    sequence: u64
+}
+
+/// # Function `load`
+///
+/// Loads the sessions saved to the file and removes it, as the sessions can only be resumed once.
+/// Problems with the file are logged and no sessions are returned, in which case the shards
+/// identify again.
+///
+/// ## Parameters
+/// - `path`, type `&Path`: the path of the file
+pub fn load(path: &Path) -> HashMap<u64, ResumeSession> {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(error) if error.kind() == ErrorKind::NotFound => return HashMap::new(),
+        Err(error) => {
+            warn!("failed to read saved gateway sessions from `{path}`: {error}", path = path.display());
+
+            return HashMap::new();
+        }
+    };
+
+    if let Err(error) = fs::remove_file(path) {
+        warn!("failed to remove saved gateway sessions file `{path}`: {error}", path = path.display());
+    }
+
+    let saved = match serde_json::from_str::<SavedSessions>(&source) {
+        Ok(saved) => saved,
+        Err(error) => {
+            warn!("failed to parse saved gateway sessions: {error}");
+
+            return HashMap::new();
+        }
+    };
+
+    if Utc::now().timestamp() - saved.saved_at > MAX_SESSION_AGE_SECS {
+        verbose!("saved gateway sessions are too old to be resumed; identifying instead");
+
+        return HashMap::new();
+    }
+
+    saved.sessions
+        .into_iter()
+        .map(|(shard_id, session)| (shard_id, ResumeSession {
+            session_id: session.session_id,
+            sequence: session.sequence
+        }))
+        .collect()
+}
+
+/// # Function `save`
+///
+/// Saves the sessions of the shards to the file; problems with the file are logged.
+///
+/// ## Parameters
+/// - `path`, type `&Path`: the path of the file
+/// - `sessions`, type `HashMap<u64, ResumeSession>`: the sessions of the shards, by shard id
+pub fn save(path: &Path, sessions: HashMap<u64, ResumeSession>) {
+    let saved = SavedSessions {
+        saved_at: Utc::now().timestamp(),
+        sessions: sessions
+            .into_iter()
+            .map(|(shard_id, session)| (shard_id, SavedSession {
+                session_id: session.session_id,
+                sequence: session.sequence
+            }))
+            .collect()
+    };
+
+    // unwrapping here is fine as serializing the sessions cannot fail
+    let json = serde_json::to_string(&saved).unwrap();
+
+    if let Err(error) = fs::write(path, json) {
+        warn!("failed to save gateway sessions to `{path}`: {error}", path = path.display());
+    }
+}
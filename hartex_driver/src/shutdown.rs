@@ -0,0 +1,135 @@
+//! # The `shutdown` Module
+//!
+//! This module provides the building blocks of the graceful shutdown of the bot: waiting for a
+//! shutdown signal, and keeping track of the spawned tasks so that they can finish before the
+//! process exits.
+
+use std::{
+    future::Future,
+    time::Duration
+};
+
+use tokio::{
+    signal,
+    sync::mpsc,
+    time
+};
+
+use hartex_logging::{
+    error,
+    warn
+};
+
+/// # Constant `SHUTDOWN_TIMEOUT`
+///
+/// How long to wait for the in-flight tasks to finish when shutting down.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// # Asynchronous Function `signal`
+///
+/// Waits until the process is asked to shut down, with Ctrl-C or `SIGTERM`.
+#[cfg(unix)]
+pub async fn signal() {
+    let mut terminate = match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+        Ok(terminate) => terminate,
+        Err(error) => {
+            error!("failed to register SIGTERM handler; only ctrl-c will shut down gracefully: {error}");
+
+            return ctrl_c().await;
+        }
+    };
+
+    tokio::select! {
+        _ = ctrl_c() => (),
+        _ = terminate.recv() => {
+            warn!("SIGTERM received; shutting down");
+        }
+    }
+}
+
+/// # Asynchronous Function `signal`
+///
+/// Waits until the process is asked to shut down with Ctrl-C.
+#[cfg(not(unix))]
+pub async fn signal() {
+    ctrl_c().await
+}
+
+/// # Asynchronous Function `ctrl_c`
+///
+/// Waits for Ctrl-C.
+async fn ctrl_c() {
+    match signal::ctrl_c().await {
+        Ok(()) => {
+            warn!("ctrl-c signal received; shutting down");
+        }
+        Err(error) => {
+            error!("failed to listen for ctrl-c; it will not shut down gracefully: {error}");
+
+            // never resolve rather than shutting down immediately
+            futures_util::future::pending::<()>().await
+        }
+    }
+}
+
+/// # Struct `TaskTracker`
+///
+/// Keeps track of spawned tasks, so that they can be waited for. Every tracked task holds a
+/// sender of a channel whose receiver is only closed once all the senders have been dropped,
+/// i.e. once all the tracked tasks have finished.
+pub struct TaskTracker {
+    sender: mpsc::Sender<()>,
+    receiver: mpsc::Receiver<()>
+}
+
+impl TaskTracker {
+    /// # Constructor `TaskTracker::new`
+    ///
+    /// Creates a new `TaskTracker`.
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel(1);
+
+        Self {
+            sender,
+            receiver
+        }
+    }
+
+    /// # Instance Method `TaskTracker::spawn`
+    ///
+    /// Spawns a tracked task.
+    ///
+    /// ## Parameters
+    /// - `future`, type `F`: the task to spawn
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future + Send + 'static {
+        let sender = self.sender.clone();
+
+        tokio::spawn(async move {
+            future.await;
+            drop(sender);
+        });
+    }
+
+    /// # Asynchronous Instance Method `TaskTracker::wait`
+    ///
+    /// Waits for all the tracked tasks to finish, for at most the given duration; returns whether
+    /// they have all finished.
+    ///
+    /// ## Parameters
+    /// - `timeout`, type `Duration`: the maximum duration to wait for
+    pub async fn wait(self, timeout: Duration) -> bool {
+        let Self { sender, mut receiver } = self;
+        drop(sender);
+
+        // nothing is ever sent; `recv` returns once every sender has been dropped
+        time::timeout(timeout, receiver.recv()).await.is_ok()
+    }
+}
+
+impl Default for TaskTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
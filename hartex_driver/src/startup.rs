@@ -0,0 +1,109 @@
+//! # The `startup` Module
+//!
+//! This module implements the tasks performed once when the bot starts up that concern the bot as
+//! a whole rather than some of its shards, such as registering the global commands. When the shards
+//! are split across several processes, only the leader process performs them; see
+//! `hartex_dbmani::coordination`.
+
+use tokio::time;
+
+use hartex_core::{
+    discord::http::Client,
+    error::HarTexResult
+};
+
+use hartex_dbmani::{
+    confcache::GuildConfigCache,
+    coordination::Leadership
+};
+
+use hartex_logging::{
+    error,
+    info,
+    verbose
+};
+
+use hartex_metrics::http::RecordHttp;
+
+use crate::{
+    commands,
+    config::{
+        BotConfig,
+        DatabaseConfig
+    }
+};
+
+/// # Struct `Role`
+///
+/// The role of the current process among the processes of the deployment.
+pub struct Role {
+    /// Whether the process is the leader, which performs the global startup tasks.
+    pub leader: bool,
+    /// The leadership held by the process, to be released when it shuts down; `None` if the process
+    /// is not the leader, or if it is the only process there can be.
+    pub leadership: Option<Leadership>
+}
+
+impl Role {
+    /// # Static Asynchronous Method `Role::elect`
+    ///
+    /// Determines the role of the current process; the first process to start up becomes the
+    /// leader and stays the leader until it shuts down.
+    ///
+    /// ## Parameters
+    /// - `config`, type `&BotConfig`: the bot-level configuration
+    pub async fn elect(config: &BotConfig) -> HarTexResult<Self> {
+        let role = match &config.database {
+            // the in-memory backend cannot be shared with other processes, so the process is the
+            // only one there can be
+            DatabaseConfig::InMemory => Self {
+                leader: true,
+                leadership: None
+            },
+            DatabaseConfig::Postgres { guildconfig_url, .. } => {
+                let leadership = Leadership::try_acquire(guildconfig_url).await?;
+
+                Self {
+                    leader: leadership.is_some(),
+                    leadership
+                }
+            }
+        };
+
+        if role.leader {
+            info!("this process is the leader; performing the global startup tasks");
+        }
+        else {
+            info!("another process is the leader; skipping the global startup tasks");
+        }
+
+        Ok(role)
+    }
+}
+
+/// # Asynchronous Function `perform_global_tasks`
+///
+/// Registers the global commands that have not been registered yet, and sets the nickname of the
+/// bot in every guild it is in to the configured one.
+///
+/// ## Parameters
+/// - `http`, type `Client`: the Twilight HTTP client
+/// - `config_cache`, type `GuildConfigCache`: the guild configuration cache to retrieve the
+///                                            configured nicknames from
+pub async fn perform_global_tasks(http: Client, config_cache: GuildConfigCache) -> HarTexResult<()> {
+    commands::register_global_commands(commands::global_commands(), http.clone(), false).await?;
+
+    for guild in http.current_user_guilds().exec().recorded("current_user_guilds").await?.models().await? {
+        verbose!("changing nickname in guild {name}", name = guild.name);
+
+        let config = config_cache.get(guild.id).await?;
+
+        if let Err(error) = http.update_current_user_nick(guild.id, &config.GuildConfiguration.nickname).exec().recorded("update_current_user_nick").await {
+            error!("failed to change nickname: {error}");
+        }
+
+        time::sleep(time::Duration::from_secs(1)).await;
+    }
+
+    Ok(())
+}
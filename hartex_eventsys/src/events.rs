@@ -17,7 +17,11 @@ use futures_util::{
     StreamExt
 };
 
-use hartex_model::payload::CommandExecuted;
+use hartex_model::payload::{
+    CommandExecuted,
+    ConfigurationReloaded,
+    ConfigurationUpdated
+};
 
 /// # Struct `Events`
 ///
@@ -60,5 +64,21 @@ pub enum HarTexEvent {
     ///
     /// ## Tuple Struct Parameters
     /// - `0`, type `Box<CommandExecuted<'a>>`: the payload of the event.
-    CommandExecuted(Box<CommandExecuted>)
+    CommandExecuted(Box<CommandExecuted>),
+
+    /// # Enum Variant `HarTexEvent::ConfigurationReloaded`
+    ///
+    /// The changed configuration of a guild has been reloaded into the running bot.
+    ///
+    /// ## Tuple Struct Parameters
+    /// - `0`, type `Box<ConfigurationReloaded>`: the payload of the event.
+    ConfigurationReloaded(Box<ConfigurationReloaded>),
+
+    /// # Enum Variant `HarTexEvent::ConfigurationUpdated`
+    ///
+    /// The configuration of a guild is updated.
+    ///
+    /// ## Tuple Struct Parameters
+    /// - `0`, type `Box<ConfigurationUpdated>`: the payload of the event.
+    ConfigurationUpdated(Box<ConfigurationUpdated>)
 }
@@ -0,0 +1,190 @@
+//! # The `filter` Module
+//!
+//! This module contains the filter deciding which messages are logged, by their log level and the
+//! module they are logged from.
+
+use std::{
+    cmp::Reverse,
+    str::FromStr
+};
+
+use crate::level::LogLevel;
+
+/// # Struct `LogFilter`
+///
+/// A filter of the messages to log: the most verbose log level to log for every module, falling
+/// back to a default log level for the modules without one.
+///
+/// A filter is written as a comma-separated list of directives, each of them either a log level,
+/// which sets the default log level, or `module=level`, which sets the log level of a module and
+/// its submodules; for example, `info,hartex_dbmani=verbose,hartex_driver::health=warn`. The most
+/// specific module directive applies.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LogFilter {
+    default: LogLevel,
+    // sorted by descending length, so that the first matching directive is the most specific one
+    modules: Vec<(String, LogLevel)>
+}
+
+impl LogFilter {
+    /// # Constructor `LogFilter::new`
+    ///
+    /// Creates a new `LogFilter` logging the messages up to the given log level from every module.
+    ///
+    /// ## Parameters
+    /// - `default`, type `LogLevel`: the most verbose log level to log
+    pub fn new(default: LogLevel) -> Self {
+        Self {
+            default,
+            modules: Vec::new()
+        }
+    }
+
+    /// # Instance Method `LogFilter::module`
+    ///
+    /// Sets the most verbose log level to log for a module and its submodules, replacing the log
+    /// level previously set for the module, if any.
+    ///
+    /// ## Parameters
+    /// - `module`, type `impl Into<String>`: the path of the module, such as `hartex_dbmani::pool`
+    /// - `level`, type `LogLevel`: the most verbose log level to log for the module
+    pub fn module(mut self, module: impl Into<String>, level: LogLevel) -> Self {
+        let module = module.into();
+
+        self.modules.retain(|(existing, _)| *existing != module);
+        self.modules.push((module, level));
+        self.modules.sort_by_key(|(module, _)| Reverse(module.len()));
+
+        self
+    }
+
+    /// # Instance Method `LogFilter::default_level`
+    ///
+    /// Returns the most verbose log level to log for the modules without a log level of their own.
+    pub fn default_level(&self) -> LogLevel {
+        self.default
+    }
+
+    /// # Instance Method `LogFilter::set_default_level`
+    ///
+    /// Sets the most verbose log level to log for the modules without a log level of their own.
+    ///
+    /// ## Parameters
+    /// - `level`, type `LogLevel`: the most verbose log level to log
+    pub fn set_default_level(&mut self, level: LogLevel) {
+        self.default = level;
+    }
+
+    /// # Instance Method `LogFilter::level_for`
+    ///
+    /// Returns the most verbose log level to log for a module.
+    ///
+    /// ## Parameters
+    /// - `module`, type `&str`: the path of the module
+    pub fn level_for(&self, module: &str) -> LogLevel {
+        self.modules
+            .iter()
+            .find(|(directive, _)| {
+                module
+                    .strip_prefix(directive.as_str())
+                    .map(|rest| rest.is_empty() || rest.starts_with("::"))
+                    .unwrap_or(false)
+            })
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+
+    /// # Instance Method `LogFilter::enabled`
+    ///
+    /// Returns whether a message of the given log level from the given module is logged.
+    ///
+    /// ## Parameters
+    /// - `level`, type `LogLevel`: the log level of the message
+    /// - `module`, type `&str`: the path of the module the message is logged from
+    pub fn enabled(&self, level: LogLevel, module: &str) -> bool {
+        level.verbosity() <= self.level_for(module).verbosity()
+    }
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        // every message is logged by default
+        Self::new(LogLevel::Verbose)
+    }
+}
+
+impl FromStr for LogFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::default();
+
+        for directive in s.split(',').map(str::trim).filter(|directive| !directive.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    let module = module.trim();
+
+                    if module.is_empty() {
+                        return Err(format!("missing module in log filter directive `{directive}`"));
+                    }
+
+                    filter = filter.module(module, level.trim().parse()?);
+                }
+                None => filter.default = directive.parse()?
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::level::LogLevel;
+
+    use super::LogFilter;
+
+    #[test]
+    fn parses_a_default_only_filter() {
+        let filter = "info".parse::<LogFilter>().unwrap();
+
+        assert_eq!(filter, LogFilter::new(LogLevel::Info));
+        assert_eq!(filter.level_for("hartex_driver::health"), LogLevel::Info);
+    }
+
+    #[test]
+    fn parses_module_directives() {
+        let filter = "warn, hartex_dbmani = verbose".parse::<LogFilter>().unwrap();
+
+        assert_eq!(filter.default_level(), LogLevel::Warn);
+        assert_eq!(filter.level_for("hartex_dbmani"), LogLevel::Verbose);
+        assert_eq!(filter.level_for("hartex_dbmani::pool"), LogLevel::Verbose);
+        assert_eq!(filter.level_for("hartex_driver"), LogLevel::Warn);
+    }
+
+    #[test]
+    fn applies_the_most_specific_module_directive() {
+        let filter = "hartex_driver::health=warn,info,hartex_driver=debug".parse::<LogFilter>().unwrap();
+
+        assert_eq!(filter.level_for("hartex_driver::health"), LogLevel::Warn);
+        assert_eq!(filter.level_for("hartex_driver::health::server"), LogLevel::Warn);
+        assert_eq!(filter.level_for("hartex_driver::events"), LogLevel::Debug);
+        assert_eq!(filter.level_for("hartex_plugins"), LogLevel::Info);
+    }
+
+    #[test]
+    fn matches_modules_at_path_boundaries_only() {
+        let filter = "info,hartex_db=error".parse::<LogFilter>().unwrap();
+
+        assert_eq!(filter.level_for("hartex_db::pool"), LogLevel::Error);
+        assert_eq!(filter.level_for("hartex_dbmani"), LogLevel::Info);
+        assert_eq!(filter.level_for("hartex_dbmani::pool"), LogLevel::Info);
+    }
+
+    #[test]
+    fn rejects_directives_without_a_level_or_module() {
+        assert!("foo=".parse::<LogFilter>().is_err());
+        assert!("=info".parse::<LogFilter>().is_err());
+        assert!("loud".parse::<LogFilter>().is_err());
+    }
+}
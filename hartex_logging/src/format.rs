@@ -0,0 +1,39 @@
+//! # The `format` Module
+//!
+//! This module contains the formats the logged messages can be written in.
+
+use std::str::FromStr;
+
+/// # Enum `LogFormat`
+///
+/// The format the logged messages are written in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LogFormat {
+    /// # Enum Variant `LogFormat::Text`
+    ///
+    /// Bracketed lines meant to be read by humans, with coloured log levels where supported.
+    Text,
+
+    /// # Enum Variant `LogFormat::Json`
+    ///
+    /// One JSON object per line, meant to be read by log aggregators.
+    Json
+}
+
+impl Default for LogFormat {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &*s.to_ascii_lowercase() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown log format `{s}`; expected one of `text` or `json`"))
+        }
+    }
+}
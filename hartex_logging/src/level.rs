@@ -2,11 +2,15 @@
 //!
 //! This module contains definitions for various log levels used for the logger.
 
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    str::FromStr
+};
 
 /// # Enum `LogLevel`
 ///
 /// An enumeration represents various log levels used within the logger for the HarTex Discord bot.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum LogLevel {
     /// # Enum Variant `LogLevel::Info`
     ///
@@ -41,6 +45,50 @@ pub enum LogLevel {
 }
 
 impl LogLevel {
+    /// # Instance Method `LogLevel::verbosity`
+    ///
+    /// Returns how verbose the log level is; a log level with a higher verbosity includes the
+    /// messages of all the log levels with a lower verbosity.
+    pub fn verbosity(self) -> u8 {
+        match self {
+            Self::Error => 0,
+            Self::Warn => 1,
+            Self::Info => 2,
+            Self::Debug => 3,
+            Self::Verbose => 4
+        }
+    }
+
+    /// # Static Method `LogLevel::from_verbosity`
+    ///
+    /// Returns the log level with the given verbosity; verbosities higher than that of
+    /// `LogLevel::Verbose` are clamped to it.
+    ///
+    /// ## Parameters
+    /// - `verbosity`, type `u8`: the verbosity
+    pub fn from_verbosity(verbosity: u8) -> Self {
+        match verbosity {
+            0 => Self::Error,
+            1 => Self::Warn,
+            2 => Self::Info,
+            3 => Self::Debug,
+            _ => Self::Verbose
+        }
+    }
+
+    /// # Instance Method `LogLevel::name`
+    ///
+    /// Returns the name of the log level, as it is written in the configuration.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Debug => "debug",
+            Self::Warn => "warn",
+            Self::Error => "error",
+            Self::Verbose => "verbose"
+        }
+    }
+
     pub fn display(self) -> impl Display {
         match self {
             Self::Info => "INFO   ",
@@ -51,3 +99,18 @@ impl LogLevel {
         }
     }
 }
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &*s.to_ascii_lowercase() {
+            "error" => Ok(Self::Error),
+            "warn" => Ok(Self::Warn),
+            "info" => Ok(Self::Info),
+            "debug" => Ok(Self::Debug),
+            "verbose" => Ok(Self::Verbose),
+            _ => Err(format!("unknown log level `{s}`; expected one of `error`, `warn`, `info`, `debug` or `verbose`"))
+        }
+    }
+}
@@ -1,29 +1,141 @@
 //! # `hartex_logging` - The HarTex Logging Library
 //!
 //! The `hartex_logging` library contains an implementation of a logger for the HarTex Discord bot.
+//!
+//! Messages are logged with the macros of the library, filtered by their log level and the module
+//! they are logged from, and written to one or more sinks such as the standard output or a file.
 
 #![feature(format_args_capture)]
 
-use hartex_core::{
-    ansi::{
-        ansi_display,
-        AnsiColour,
-        SgrParam
-    },
-    time::Local
+use std::sync::{
+    PoisonError,
+    RwLock
+};
+
+use once_cell::sync::{
+    Lazy,
+    OnceCell
+};
+
+use hartex_core::time::Local;
+
+use crate::{
+    filter::LogFilter,
+    format::LogFormat,
+    record::LogRecord,
+    sink::{
+        LogSink,
+        StdoutSink
+    }
 };
 
+pub mod filter;
+pub mod format;
 pub mod level;
+pub mod macros;
+pub mod record;
+pub mod sink;
+
+/// # Static `FILTER`
+///
+/// The filter of the messages to log; every message is logged by default.
+static FILTER: Lazy<RwLock<LogFilter>> = Lazy::new(|| RwLock::new(LogFilter::default()));
+
+/// # Static `SINKS`
+///
+/// The sinks the logged messages are written to; the standard output, in the text format, by
+/// default.
+static SINKS: Lazy<RwLock<Vec<Box<dyn LogSink + Send + Sync>>>> = Lazy::new(|| RwLock::new(vec![Box::new(StdoutSink::new(LogFormat::Text))]));
+
+/// # Static `PROCESS`
+///
+/// The name of the current process, included in every message so that the messages of the
+/// processes of a deployment running several of them can be told apart.
+static PROCESS: OnceCell<String> = OnceCell::new();
 
 /// # Struct `Logger`
 ///
 /// The main logger that HarTex Discord bot uses.
+///
+/// The logging macros (`info!`, `warn!` and so on) are the preferred way to log messages, as they
+/// capture the location they are invoked from.
 pub struct Logger;
 
 impl Logger {
+    /// # Static Method `Logger::set_level`
+    ///
+    /// Sets the most verbose log level that is logged for the modules without a log level of their
+    /// own; messages of more verbose log levels are discarded from then on.
+    ///
+    /// ## Parameters
+    /// - `log_level`, type `level::LogLevel`: the most verbose log level to log
+    pub fn set_level(log_level: level::LogLevel) {
+        FILTER.write().unwrap_or_else(PoisonError::into_inner).set_default_level(log_level);
+    }
+
+    /// # Static Method `Logger::level`
+    ///
+    /// Returns the most verbose log level that is logged for the modules without a log level of
+    /// their own.
+    pub fn level() -> level::LogLevel {
+        FILTER.read().unwrap_or_else(PoisonError::into_inner).default_level()
+    }
+
+    /// # Static Method `Logger::set_filter`
+    ///
+    /// Sets the filter of the messages to log, replacing the current one; may be called at any
+    /// time.
+    ///
+    /// ## Parameters
+    /// - `filter`, type `LogFilter`: the filter
+    pub fn set_filter(filter: LogFilter) {
+        *FILTER.write().unwrap_or_else(PoisonError::into_inner) = filter;
+    }
+
+    /// # Static Method `Logger::filter`
+    ///
+    /// Returns the filter of the messages to log.
+    pub fn filter() -> LogFilter {
+        FILTER.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// # Static Method `Logger::set_sinks`
+    ///
+    /// Sets the sinks the logged messages are written to, replacing the current ones; the messages
+    /// buffered by the current sinks are written out first.
+    ///
+    /// ## Parameters
+    /// - `sinks`, type `Vec<Box<dyn LogSink + Send + Sync>>`: the sinks
+    pub fn set_sinks(sinks: Vec<Box<dyn LogSink + Send + Sync>>) {
+        let mut current = SINKS.write().unwrap_or_else(PoisonError::into_inner);
+
+        current.iter().for_each(|sink| sink.flush());
+        *current = sinks;
+    }
+
+    /// # Static Method `Logger::set_process`
+    ///
+    /// Sets the name of the current process, which is included in every message logged from then
+    /// on; the name can only be set once, later calls are ignored.
+    ///
+    /// ## Parameters
+    /// - `name`, type `impl Into<String>`: the name of the process
+    pub fn set_process(name: impl Into<String>) {
+        let _ = PROCESS.set(name.into());
+    }
+
+    /// # Static Method `Logger::flush`
+    ///
+    /// Flushes the messages that have been logged but not yet written out; called before the
+    /// process exits.
+    pub fn flush() {
+        SINKS.read().unwrap_or_else(PoisonError::into_inner).iter().for_each(|sink| sink.flush());
+    }
+
     /// # Static Method `Logger:log`
     ///
-    /// Logs a message to the console with the provided log level (the `log_level` parameter).
+    /// Logs a message with the provided log level (the `log_level` parameter), if the filter lets
+    /// it through.
     ///
     /// ## Parameters
     ///
@@ -32,32 +144,50 @@ impl Logger {
     /// - `module`, type `Option<&'static str>`: the module where the static method is invoked; can be `None`,
     ///                                          and defaults to the `hartex_logging` module.
     pub fn log(message: impl Into<String>, log_level: level::LogLevel, module: Option<&'static str>, file: &'static str, line: u32, column: u32) {
-        let module_name = module.unwrap_or(module_path!());
-        let mut params = SgrParam::BoldOrIncreasedIntensity.into_i32s();
-
-        params.append(&mut SgrParam::SetColour {
-            colour: AnsiColour::CustomU8 {
-                n: match log_level {
-                    level::LogLevel::Info => 2,
-                    level::LogLevel::Debug => 33,
-                    level::LogLevel::Warn => 226,
-                    level::LogLevel::Error => 1,
-                    level::LogLevel::Verbose => 240
-                }
-            },
-            foreground: true
-        }.into_i32s());
-
-        println!(
-            "[HarTex v{version}: {now}+08:00] [{ansi}{level}{reset}] [{module_name}] [{file}:{line}:{column}] {message}",
-            version = env!("CARGO_PKG_VERSION"),
-            now = Local::now().format("%Y-%m-%d %H:%M:%S"),
-            ansi = ansi_display(params),
-            level = log_level.display(),
-            reset = ansi_display(SgrParam::Reset.into_i32s()),
-            file = file.strip_prefix(r"D:\Projects\HarTexBot\HarTex-rust-discord-bot\").unwrap().replace(r"\", "/"),
-            message = message.into()
-        );
+        Self::log_with_fields(message, &[], log_level, module, file, line, column)
+    }
+
+    /// # Static Method `Logger::log_with_fields`
+    ///
+    /// Logs a message with the provided log level and structured key-value fields, if the filter
+    /// lets it through.
+    ///
+    /// ## Parameters
+    /// - `message`, type `impl Into<String>`: the message to be logged
+    /// - `fields`, type `&[(&'static str, String)]`: the structured fields of the message, such as
+    ///                                               the id of the guild it concerns
+    /// - `log_level`, type `level::LogLevel`: the log level to use
+    /// - `module`, type `Option<&'static str>`: the module where the static method is invoked; can be `None`,
+    ///                                          and defaults to the `hartex_logging` module.
+    pub fn log_with_fields(
+        message: impl Into<String>,
+        fields: &[(&'static str, String)],
+        log_level: level::LogLevel,
+        module: Option<&'static str>,
+        file: &'static str,
+        line: u32,
+        column: u32
+    ) {
+        let module = module.unwrap_or(module_path!());
+
+        if !FILTER.read().unwrap_or_else(PoisonError::into_inner).enabled(log_level, module) {
+            return;
+        }
+
+        let message = message.into();
+        let record = LogRecord {
+            level: log_level,
+            message: &message,
+            module,
+            file,
+            line,
+            column,
+            timestamp: Local::now(),
+            process: PROCESS.get().map(String::as_str),
+            fields
+        };
+
+        SINKS.read().unwrap_or_else(PoisonError::into_inner).iter().for_each(|sink| sink.write(&record));
     }
 
     /// # Static Method `Logger::info`
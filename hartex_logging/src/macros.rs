@@ -0,0 +1,92 @@
+//! # The `macros` Module
+//!
+//! This module contains the logging macros, which log a message formatted like with `format!`
+//! along with the location they are invoked from.
+//!
+//! The message can be preceded by structured key-value fields, separated from it by a semicolon;
+//! the values are converted to strings with their `Display` implementations.
+//!
+//! ```ignore
+//! use hartex_logging::{info, warn};
+//!
+//! info!("guild {guild_id} is whitelisted");
+//! warn!(shard_id = shard_id; "failed to set presence: {error}");
+//! ```
+
+/// # Macro `log!`
+///
+/// Logs a formatted message with the given log level, optionally with structured fields.
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($key:ident = $value:expr),+; $($argument:tt)+) => {
+        $crate::Logger::log_with_fields(
+            ::std::format!($($argument)+),
+            &[$((::std::stringify!($key), ::std::string::ToString::to_string(&$value))),+],
+            $level,
+            ::std::option::Option::Some(::std::module_path!()),
+            ::std::file!(),
+            ::std::line!(),
+            ::std::column!()
+        )
+    };
+    ($level:expr, $($argument:tt)+) => {
+        $crate::Logger::log(
+            ::std::format!($($argument)+),
+            $level,
+            ::std::option::Option::Some(::std::module_path!()),
+            ::std::file!(),
+            ::std::line!(),
+            ::std::column!()
+        )
+    };
+}
+
+/// # Macro `info!`
+///
+/// Logs a formatted message with the "information" log level.
+#[macro_export]
+macro_rules! info {
+    ($($argument:tt)+) => {
+        $crate::log!($crate::level::LogLevel::Info, $($argument)+)
+    };
+}
+
+/// # Macro `debug!`
+///
+/// Logs a formatted message with the "debug" log level.
+#[macro_export]
+macro_rules! debug {
+    ($($argument:tt)+) => {
+        $crate::log!($crate::level::LogLevel::Debug, $($argument)+)
+    };
+}
+
+/// # Macro `warn!`
+///
+/// Logs a formatted message with the "warning" log level.
+#[macro_export]
+macro_rules! warn {
+    ($($argument:tt)+) => {
+        $crate::log!($crate::level::LogLevel::Warn, $($argument)+)
+    };
+}
+
+/// # Macro `error!`
+///
+/// Logs a formatted message with the "error" log level.
+#[macro_export]
+macro_rules! error {
+    ($($argument:tt)+) => {
+        $crate::log!($crate::level::LogLevel::Error, $($argument)+)
+    };
+}
+
+/// # Macro `verbose!`
+///
+/// Logs a formatted message with the "verbose" log level.
+#[macro_export]
+macro_rules! verbose {
+    ($($argument:tt)+) => {
+        $crate::log!($crate::level::LogLevel::Verbose, $($argument)+)
+    };
+}
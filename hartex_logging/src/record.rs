@@ -0,0 +1,277 @@
+//! # The `record` Module
+//!
+//! This module contains the definition of a logged message as it is passed to the sinks, and its
+//! formatting.
+
+use std::path::Path;
+
+use serde_json::{
+    Map,
+    Value
+};
+
+use hartex_core::{
+    ansi::{
+        ansi_display,
+        AnsiColour,
+        SgrParam
+    },
+    time::{
+        DateTime,
+        Local,
+        SecondsFormat
+    }
+};
+
+use crate::{
+    format::LogFormat,
+    level::LogLevel
+};
+
+/// # Struct `LogRecord`
+///
+/// A logged message, along with where and when it is logged from.
+pub struct LogRecord<'a> {
+    pub level: LogLevel,
+    pub message: &'a str,
+    pub module: &'a str,
+    pub file: &'static str,
+    pub line: u32,
+    pub column: u32,
+    pub timestamp: DateTime<Local>,
+    /// The name of the process, if it is set.
+    pub process: Option<&'a str>,
+    /// The structured key-value fields of the message, such as the id of the guild it concerns.
+    pub fields: &'a [(&'static str, String)]
+}
+
+impl LogRecord<'_> {
+    /// # Instance Method `LogRecord::render`
+    ///
+    /// Formats the record as a line in the given format, without the line terminator.
+    ///
+    /// ## Parameters
+    /// - `format`, type `LogFormat`: the format to use
+    /// - `coloured`, type `bool`: whether to colour the log level with ANSI escape sequences; only
+    ///                            used by the text format
+    pub fn render(&self, format: LogFormat, coloured: bool) -> String {
+        match format {
+            LogFormat::Text => self.format(coloured),
+            LogFormat::Json => self.format_json()
+        }
+    }
+
+    /// # Instance Method `LogRecord::format`
+    ///
+    /// Formats the record as a bracketed line meant to be read by humans, without the line
+    /// terminator.
+    ///
+    /// ## Parameters
+    /// - `coloured`, type `bool`: whether to colour the log level with ANSI escape sequences
+    pub fn format(&self, coloured: bool) -> String {
+        let (ansi, reset) = if coloured {
+            let mut params = SgrParam::BoldOrIncreasedIntensity.into_i32s();
+
+            params.append(&mut SgrParam::SetColour {
+                colour: AnsiColour::CustomU8 {
+                    n: match self.level {
+                        LogLevel::Info => 2,
+                        LogLevel::Debug => 33,
+                        LogLevel::Warn => 226,
+                        LogLevel::Error => 1,
+                        LogLevel::Verbose => 240
+                    }
+                },
+                foreground: true
+            }.into_i32s());
+
+            (ansi_display(params).to_string(), ansi_display(SgrParam::Reset.into_i32s()).to_string())
+        }
+        else {
+            (String::new(), String::new())
+        };
+
+        let process = self.process
+            .map(|process| format!(" [{process}]"))
+            .unwrap_or_default();
+        let fields = if self.fields.is_empty() {
+            String::new()
+        }
+        else {
+            let fields = self.fields
+                .iter()
+                .map(|(key, value)| format!("{key}: {value}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!(" [{fields}]")
+        };
+
+        format!(
+            "[HarTex v{version}: {now}]{process} [{ansi}{level}{reset}] [{module}] [{file}:{line}:{column}] {message}{fields}",
+            version = env!("CARGO_PKG_VERSION"),
+            now = self.timestamp.format("%Y-%m-%d %H:%M:%S%:z"),
+            level = self.level.display(),
+            module = self.module,
+            file = self.source_file(),
+            line = self.line,
+            column = self.column,
+            message = self.message
+        )
+    }
+
+    /// # Instance Method `LogRecord::format_json`
+    ///
+    /// Formats the record as a single-line JSON object meant to be read by log aggregators,
+    /// without the line terminator.
+    pub fn format_json(&self) -> String {
+        let mut object = Map::new();
+
+        object.insert(String::from("timestamp"), Value::from(self.timestamp.to_rfc3339_opts(SecondsFormat::Millis, false)));
+        object.insert(String::from("level"), Value::from(self.level.name()));
+
+        if let Some(process) = self.process {
+            object.insert(String::from("process"), Value::from(process));
+        }
+
+        object.insert(String::from("module"), Value::from(self.module));
+        object.insert(String::from("file"), Value::from(self.source_file()));
+        object.insert(String::from("line"), Value::from(self.line));
+        object.insert(String::from("column"), Value::from(self.column));
+        object.insert(String::from("message"), Value::from(self.message));
+
+        if !self.fields.is_empty() {
+            let fields = self.fields
+                .iter()
+                .map(|(key, value)| (String::from(*key), Value::from(value.as_str())))
+                .collect::<Map<_, _>>();
+
+            object.insert(String::from("fields"), Value::Object(fields));
+        }
+
+        Value::Object(object).to_string()
+    }
+
+    /// # Instance Method `LogRecord::source_file`
+    ///
+    /// Returns the path of the source file the message is logged from, as it is displayed: with
+    /// forward slashes on every platform, and relative to the repository, except for the source
+    /// files of the crate being built, which stay relative to that crate.
+    fn source_file(&self) -> String {
+        repository_relative(self.file)
+    }
+}
+
+/// # Function `repository_relative`
+///
+/// Returns the path of a source file relative to the repository, with forward slashes.
+///
+/// The crates of the bot are not members of a Cargo workspace; each of them is built on its own
+/// (such as `cd hartex && cargo build`). `file!()` therefore returns paths relative to the crate
+/// being built for its own source files, but absolute paths for those of the sibling crates it
+/// depends on by path, which are made relative to the repository here.
+///
+/// The relative paths of the crate being built are returned as they are, apart from their
+/// separators: a message logged from the `hartex` binary itself is displayed as coming from
+/// `src/main.rs` rather than `hartex/src/main.rs`, as the crate directory is not known here. Paths
+/// outside of the repository are also returned as they are, apart from their separators.
+///
+/// ## Parameters
+/// - `file`, type `&str`: the path of the source file, as returned by `file!()`
+fn repository_relative(file: &str) -> String {
+    let file = file.replace('\\', "/");
+    // the repository is the parent directory of this crate
+    let repository = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(|repository| format!("{}/", repository.display().to_string().replace('\\', "/")));
+
+    match repository.as_deref().and_then(|repository| file.strip_prefix(repository)) {
+        Some(relative) => relative.to_string(),
+        None => file
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::Value;
+
+    use hartex_core::time::{
+        Local,
+        SecondsFormat
+    };
+
+    use crate::level::LogLevel;
+
+    use super::{
+        repository_relative,
+        LogRecord
+    };
+
+    /// # Function `repository`
+    ///
+    /// Returns the path of the repository this crate is in, with forward slashes.
+    fn repository() -> String {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .display()
+            .to_string()
+            .replace('\\', "/")
+    }
+
+    #[test]
+    fn absolute_path_in_repository_is_made_relative() {
+        let file = format!("{}/hartex_driver/src/lib.rs", repository());
+
+        assert_eq!(repository_relative(&file), "hartex_driver/src/lib.rs");
+    }
+
+    #[test]
+    fn backslash_separated_path_in_repository_is_made_relative() {
+        let file = format!(r"{}\hartex_driver\src\lib.rs", repository().replace('/', r"\"));
+
+        assert_eq!(repository_relative(&file), "hartex_driver/src/lib.rs");
+    }
+
+    #[test]
+    fn path_outside_repository_is_unchanged() {
+        assert_eq!(repository_relative("/elsewhere/hartex/src/main.rs"), "/elsewhere/hartex/src/main.rs");
+    }
+
+    #[test]
+    fn crate_relative_path_stays_crate_relative() {
+        assert_eq!(repository_relative("src/main.rs"), "src/main.rs");
+        assert_eq!(repository_relative(r"src\commands\mod.rs"), "src/commands/mod.rs");
+    }
+
+    #[test]
+    fn timestamps_carry_the_local_offset() {
+        let timestamp = Local::now();
+        let file = format!("{}/hartex_logging/src/record.rs", repository());
+        // the file of a record is a `&'static str`, as returned by `file!()`
+        let file: &'static str = Box::leak(file.into_boxed_str());
+        let record = LogRecord {
+            level: LogLevel::Info,
+            message: "message",
+            module: module_path!(),
+            file,
+            line: 1,
+            column: 1,
+            timestamp,
+            process: None,
+            fields: &[("guild_id", String::from("1234"))]
+        };
+
+        let text = record.format(false);
+        assert!(text.contains(&format!("{}]", timestamp.format("%Y-%m-%d %H:%M:%S%:z"))), "{text}");
+        assert!(text.contains("[hartex_logging/src/record.rs:1:1]"), "{text}");
+
+        let json = record.format_json().parse::<Value>().unwrap();
+        assert_eq!(json["timestamp"], timestamp.to_rfc3339_opts(SecondsFormat::Millis, false));
+        assert!(json["timestamp"].as_str().unwrap().ends_with(&timestamp.format("%:z").to_string()));
+        assert_eq!(json["file"], "hartex_logging/src/record.rs");
+        assert_eq!(json["fields"]["guild_id"], "1234");
+    }
+}
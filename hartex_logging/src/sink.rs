@@ -0,0 +1,160 @@
+//! # The `sink` Module
+//!
+//! This module contains the sinks the logged messages are written to, such as the standard output
+//! or a file.
+
+use std::{
+    env,
+    fs::{
+        File,
+        OpenOptions
+    },
+    io::{
+        self,
+        LineWriter,
+        Write
+    },
+    path::Path,
+    sync::Mutex
+};
+
+use atty::Stream;
+
+use crate::{
+    format::LogFormat,
+    record::LogRecord
+};
+
+/// # Trait `LogSink`
+///
+/// A destination of the logged messages.
+///
+/// ## Trait Methods
+/// - `write`; parameters `&LogRecord`: writes a logged message
+/// - `flush`: writes out the messages that have been written but are still buffered
+pub trait LogSink {
+    fn write(&self, record: &LogRecord);
+
+    fn flush(&self) {}
+}
+
+/// # Struct `StdoutSink`
+///
+/// Writes the logged messages to the standard output, with coloured log levels if the standard
+/// output is a terminal.
+pub struct StdoutSink {
+    format: LogFormat,
+    coloured: bool
+}
+
+impl StdoutSink {
+    /// # Constructor `StdoutSink::new`
+    ///
+    /// Creates a new `StdoutSink` writing the messages in the given format.
+    ///
+    /// ## Parameters
+    /// - `format`, type `LogFormat`: the format to write the messages in
+    pub fn new(format: LogFormat) -> Self {
+        Self {
+            format,
+            coloured: colours_supported(Stream::Stdout)
+        }
+    }
+}
+
+impl LogSink for StdoutSink {
+    fn write(&self, record: &LogRecord) {
+        // there is nowhere to report a failure to write to
+        let _ = writeln!(io::stdout().lock(), "{line}", line = record.render(self.format, self.coloured));
+    }
+
+    fn flush(&self) {
+        let _ = io::stdout().flush();
+    }
+}
+
+/// # Struct `StderrSink`
+///
+/// Writes the logged messages to the standard error, with coloured log levels if the standard
+/// error is a terminal.
+pub struct StderrSink {
+    format: LogFormat,
+    coloured: bool
+}
+
+impl StderrSink {
+    /// # Constructor `StderrSink::new`
+    ///
+    /// Creates a new `StderrSink` writing the messages in the given format.
+    ///
+    /// ## Parameters
+    /// - `format`, type `LogFormat`: the format to write the messages in
+    pub fn new(format: LogFormat) -> Self {
+        Self {
+            format,
+            coloured: colours_supported(Stream::Stderr)
+        }
+    }
+}
+
+impl LogSink for StderrSink {
+    fn write(&self, record: &LogRecord) {
+        let _ = writeln!(io::stderr().lock(), "{line}", line = record.render(self.format, self.coloured));
+    }
+
+    fn flush(&self) {
+        let _ = io::stderr().flush();
+    }
+}
+
+/// # Struct `FileSink`
+///
+/// Appends the logged messages to a file, without colours.
+pub struct FileSink {
+    file: Mutex<LineWriter<File>>,
+    format: LogFormat
+}
+
+impl FileSink {
+    /// # Static Method `FileSink::open`
+    ///
+    /// Opens the file to append the logged messages to, creating it if it does not exist.
+    ///
+    /// ## Parameters
+    /// - `path`, type `impl AsRef<Path>`: the path of the file
+    /// - `format`, type `LogFormat`: the format to write the messages in
+    pub fn open(path: impl AsRef<Path>, format: LogFormat) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            file: Mutex::new(LineWriter::new(file)),
+            format
+        })
+    }
+}
+
+impl LogSink for FileSink {
+    fn write(&self, record: &LogRecord) {
+        // a panic while the lock is held cannot leave the writer in an invalid state
+        let mut file = self.file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let _ = writeln!(file, "{line}", line = record.render(self.format, false));
+    }
+
+    fn flush(&self) {
+        let _ = self.file.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).flush();
+    }
+}
+
+/// # Function `colours_supported`
+///
+/// Returns whether the log levels written to a standard stream are coloured: the stream has to be
+/// a terminal, and the `NO_COLOR` environment variable must not be set.
+///
+/// ## Parameters
+/// - `stream`, type `Stream`: the standard stream
+fn colours_supported(stream: Stream) -> bool {
+    let no_color = env::var_os("NO_COLOR").map(|value| !value.is_empty()).unwrap_or(false);
+
+    !no_color && atty::is(stream)
+}
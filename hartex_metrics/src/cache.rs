@@ -0,0 +1,28 @@
+//! # The `cache` Module
+//!
+//! This module records the metrics of the caches: how many entries they hold.
+
+use once_cell::sync::Lazy;
+
+use prometheus::{
+    IntGaugeVec,
+    Opts
+};
+
+/// # Static `SIZE`
+///
+/// The number of entries of every cached resource.
+static SIZE: Lazy<IntGaugeVec> = Lazy::new(|| crate::register(
+    IntGaugeVec::new(Opts::new("cache_size", "Entries of the cached resource"), &["resource"]).unwrap()
+));
+
+/// # Function `set_size`
+///
+/// Sets the number of entries of a cached resource.
+///
+/// ## Parameters
+/// - `resource`, type `&str`: the cached resource
+/// - `size`, type `usize`: the number of entries
+pub fn set_size(resource: &str, size: usize) {
+    SIZE.with_label_values(&[resource]).set(size as i64);
+}
@@ -0,0 +1,53 @@
+//! # The `commands` Module
+//!
+//! This module records the metrics of the commands: how often they are invoked, how often they
+//! fail and how long they take.
+
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+
+use prometheus::{
+    HistogramOpts,
+    HistogramVec,
+    IntCounterVec,
+    Opts
+};
+
+/// # Static `INVOCATIONS`
+///
+/// The number of times every command has been invoked.
+static INVOCATIONS: Lazy<IntCounterVec> = Lazy::new(|| crate::register(
+    IntCounterVec::new(Opts::new("command_invocations_total", "Command invocations"), &["command"]).unwrap()
+));
+
+/// # Static `FAILURES`
+///
+/// The number of times every command has failed.
+static FAILURES: Lazy<IntCounterVec> = Lazy::new(|| crate::register(
+    IntCounterVec::new(Opts::new("command_failures_total", "Command invocations that failed"), &["command"]).unwrap()
+));
+
+/// # Static `DURATION`
+///
+/// The duration of the executions of every command.
+static DURATION: Lazy<HistogramVec> = Lazy::new(|| crate::register(
+    HistogramVec::new(HistogramOpts::new("command_duration_seconds", "Duration of the command executions"), &["command"]).unwrap()
+));
+
+/// # Function `record`
+///
+/// Records an execution of a command.
+///
+/// ## Parameters
+/// - `command`, type `&str`: the name of the command
+/// - `duration`, type `Duration`: how long the execution took
+/// - `succeeded`, type `bool`: whether the execution succeeded
+pub fn record(command: &str, duration: Duration, succeeded: bool) {
+    INVOCATIONS.with_label_values(&[command]).inc();
+    DURATION.with_label_values(&[command]).observe(duration.as_secs_f64());
+
+    if !succeeded {
+        FAILURES.with_label_values(&[command]).inc();
+    }
+}
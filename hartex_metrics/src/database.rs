@@ -0,0 +1,40 @@
+//! # The `database` Module
+//!
+//! This module records the metrics of the database: how long the queries take.
+
+use std::{
+    future::Future,
+    time::Instant
+};
+
+use once_cell::sync::Lazy;
+
+use prometheus::{
+    HistogramOpts,
+    HistogramVec
+};
+
+/// # Static `QUERY_DURATION`
+///
+/// The duration of every kind of database query.
+static QUERY_DURATION: Lazy<HistogramVec> = Lazy::new(|| crate::register(
+    HistogramVec::new(HistogramOpts::new("database_query_duration_seconds", "Duration of the database queries"), &["query"]).unwrap()
+));
+
+/// # Asynchronous Function `timed`
+///
+/// Runs a database query, recording how long it takes.
+///
+/// ## Parameters
+/// - `query`, type `&'static str`: the kind of the query
+/// - `future`, type `F`: the query
+pub async fn timed<F>(query: &'static str, future: F) -> F::Output
+where
+    F: Future {
+    let start = Instant::now();
+    let output = future.await;
+
+    QUERY_DURATION.with_label_values(&[query]).observe(start.elapsed().as_secs_f64());
+
+    output
+}
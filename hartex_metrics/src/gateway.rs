@@ -0,0 +1,64 @@
+//! # The `gateway` Module
+//!
+//! This module records the metrics of the gateway: the events received and the status of the
+//! shards.
+
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+
+use prometheus::{
+    GaugeVec,
+    IntCounterVec,
+    IntGaugeVec,
+    Opts
+};
+
+/// # Static `EVENTS`
+///
+/// The number of gateway events received, by event type.
+static EVENTS: Lazy<IntCounterVec> = Lazy::new(|| crate::register(
+    IntCounterVec::new(Opts::new("gateway_events_total", "Gateway events received"), &["event"]).unwrap()
+));
+
+/// # Static `SHARD_LATENCY`
+///
+/// The average heartbeat latency of every shard.
+static SHARD_LATENCY: Lazy<GaugeVec> = Lazy::new(|| crate::register(
+    GaugeVec::new(Opts::new("shard_latency_seconds", "Average heartbeat latency of the shard"), &["shard"]).unwrap()
+));
+
+/// # Static `SHARD_GUILDS`
+///
+/// The number of guilds of every shard.
+static SHARD_GUILDS: Lazy<IntGaugeVec> = Lazy::new(|| crate::register(
+    IntGaugeVec::new(Opts::new("shard_guilds", "Guilds of the shard"), &["shard"]).unwrap()
+));
+
+/// # Function `record_event`
+///
+/// Records a gateway event received.
+///
+/// ## Parameters
+/// - `event`, type `&str`: the type of the event
+pub fn record_event(event: &str) {
+    EVENTS.with_label_values(&[event]).inc();
+}
+
+/// # Function `set_shard_status`
+///
+/// Sets the status of a shard.
+///
+/// ## Parameters
+/// - `shard_id`, type `u64`: the id of the shard
+/// - `latency`, type `Option<Duration>`: the average heartbeat latency of the shard, if known
+/// - `guilds`, type `usize`: the number of guilds of the shard
+pub fn set_shard_status(shard_id: u64, latency: Option<Duration>, guilds: usize) {
+    let shard = shard_id.to_string();
+
+    if let Some(latency) = latency {
+        SHARD_LATENCY.with_label_values(&[&shard]).set(latency.as_secs_f64());
+    }
+
+    SHARD_GUILDS.with_label_values(&[&shard]).set(guilds as i64);
+}
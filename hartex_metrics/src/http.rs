@@ -0,0 +1,81 @@
+//! # The `http` Module
+//!
+//! This module records the metrics of the requests made to the Discord API: how many are made and
+//! how many Discord responded to with `429 Too Many Requests`. The requests delayed by the
+//! ratelimiter before being sent are not counted as rate limited.
+
+use std::{
+    future::Future,
+    pin::Pin
+};
+
+use once_cell::sync::Lazy;
+
+use prometheus::{
+    IntCounterVec,
+    Opts
+};
+
+use hartex_core::discord::http::{
+    error::{
+        Error,
+        ErrorType
+    },
+    response::Response
+};
+
+/// # Static `REQUESTS`
+///
+/// The number of requests made, by route and outcome; the outcome is `ok` for successful
+/// requests, the status code for requests Discord responded to with an error, and `error` for
+/// requests that failed otherwise.
+static REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| crate::register(
+    IntCounterVec::new(Opts::new("http_requests_total", "Requests made to the Discord API"), &["route", "outcome"]).unwrap()
+));
+
+/// # Static `TOO_MANY_REQUESTS_RESPONSES`
+///
+/// The number of requests Discord responded to with `429 Too Many Requests`, by route.
+static TOO_MANY_REQUESTS_RESPONSES: Lazy<IntCounterVec> = Lazy::new(|| crate::register(
+    IntCounterVec::new(
+        Opts::new("http_429_responses_total", "Requests to the Discord API responded to with 429 Too Many Requests"),
+        &["route"]
+    ).unwrap()
+));
+
+/// # Trait `RecordHttp`
+///
+/// An extension trait for the futures of requests to the Discord API, recording their outcome
+/// once they complete.
+///
+/// ## Trait Methods
+/// - `recorded`; parameters `&'static str`; return type
+///               `Pin<Box<dyn Future<Output = Result<Response<T>, Error>> + Send>>`: the request,
+///               recording its outcome under the given route once it completes
+pub trait RecordHttp<'a, T>: Future<Output = Result<Response<T>, Error>> + Send + Sized + 'a {
+    fn recorded(self, route: &'static str) -> Pin<Box<dyn Future<Output = Result<Response<T>, Error>> + Send + 'a>> {
+        Box::pin(async move {
+            let result = self.await;
+
+            let outcome = match &result {
+                Ok(_) => String::from("ok"),
+                Err(error) => match error.kind() {
+                    ErrorType::Response { status, .. } => status.raw().to_string(),
+                    _ => String::from("error")
+                }
+            };
+
+            if outcome == "429" {
+                TOO_MANY_REQUESTS_RESPONSES.with_label_values(&[route]).inc();
+            }
+
+            REQUESTS.with_label_values(&[route, &outcome]).inc();
+
+            result
+        })
+    }
+}
+
+impl<'a, T, F> RecordHttp<'a, T> for F
+where
+    F: Future<Output = Result<Response<T>, Error>> + Send + 'a {}
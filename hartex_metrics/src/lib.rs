@@ -0,0 +1,63 @@
+//! # `hartex_metrics` - The HarTex Metrics Library
+//!
+//! The `hartex_metrics` library records the metrics of the HarTex Discord bot, such as the gateway
+//! events received, the commands executed, the HTTP requests made and the database queries run,
+//! and exports them in the Prometheus text format.
+//!
+//! The metrics are recorded into a single registry for the whole process; every metric name is
+//! prefixed with `hartex_`.
+
+use once_cell::sync::Lazy;
+
+use prometheus::{
+    core::Collector,
+    Encoder,
+    Registry,
+    TextEncoder
+};
+
+pub mod cache;
+pub mod commands;
+pub mod database;
+pub mod gateway;
+pub mod http;
+
+/// # Constant `CONTENT_TYPE`
+///
+/// The content type of the exported metrics.
+pub const CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+/// # Static `REGISTRY`
+///
+/// The registry every metric of the process is recorded into.
+static REGISTRY: Lazy<Registry> = Lazy::new(|| {
+    // unwrapping here is fine as the prefix is a valid metric name
+    Registry::new_custom(Some(String::from("hartex")), None).unwrap()
+});
+
+/// # Function `gather`
+///
+/// Returns the current values of every metric, in the Prometheus text format.
+pub fn gather() -> String {
+    let mut buffer = Vec::new();
+
+    // unwrapping here is fine as encoding into a vector cannot fail, and the encoded metrics are
+    // valid UTF-8
+    TextEncoder::new().encode(&REGISTRY.gather(), &mut buffer).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+/// # Function `register`
+///
+/// Registers a metric into the registry and returns it.
+///
+/// ## Parameters
+/// - `collector`, type `C`: the metric to register
+fn register<C>(collector: C) -> C
+where
+    C: Collector + Clone + 'static {
+    // unwrapping here is fine as every metric is only registered once, by its static
+    REGISTRY.register(Box::new(collector.clone())).unwrap();
+
+    collector
+}
@@ -12,3 +12,20 @@ pub struct CommandExecuted {
     pub command: String,
     pub guild_id: GuildId
 }
+
+/// # Struct `ConfigurationUpdated`
+///
+/// The payload for which when the configuration of a guild is updated.
+#[derive(Clone)]
+pub struct ConfigurationUpdated {
+    pub guild_id: GuildId
+}
+
+/// # Struct `ConfigurationReloaded`
+///
+/// The payload for which when the changed configuration of a guild has been reloaded into the
+/// running bot.
+#[derive(Clone)]
+pub struct ConfigurationReloaded {
+    pub guild_id: GuildId
+}
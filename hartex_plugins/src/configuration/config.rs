@@ -0,0 +1,565 @@
+//! # The `config` Module
+//!
+//! This module implements the `config` command.
+
+use std::time::Duration;
+
+use similar::TextDiff;
+
+use hartex_cmdsys::{
+    command::{
+        Command,
+        CommandType
+    },
+    context::CommandContext
+};
+
+use hartex_core::{
+    discord::{
+        cache_inmemory::InMemoryCache,
+        gateway::Event,
+        model::{
+            application::{
+                callback::{
+                    CallbackData,
+                    InteractionResponse
+                },
+                command::{
+                    ChoiceCommandOptionData,
+                    CommandOption,
+                    OptionsCommandOptionData
+                },
+                component::{
+                    button::ButtonStyle,
+                    ActionRow,
+                    Button,
+                    Component
+                },
+                interaction::{
+                    application_command::CommandDataOption,
+                    Interaction
+                }
+            },
+            channel::message::MessageFlags,
+            id::{
+                ChannelId,
+                GuildId,
+                InteractionId,
+                MessageId,
+                UserId
+            }
+        }
+    },
+    error::{
+        HarTexError,
+        HarTexResult
+    }
+};
+
+use hartex_conftoml::{
+    dashacc::AccessLevel,
+    migration,
+    template,
+    validation,
+    TomlConfig
+};
+
+use hartex_eventsys::events::HarTexEvent;
+
+use hartex_metrics::http::RecordHttp;
+
+use hartex_model::payload::ConfigurationUpdated;
+
+use hartex_utils::FutureRetType;
+
+/// # Constant `CONFIRMATION_TIMEOUT`
+///
+/// The duration to wait for the user to confirm or cancel an upload.
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// # Constant `MAX_CONFIG_SIZE`
+///
+/// The maximum size of an uploaded configuration file, in bytes.
+const MAX_CONFIG_SIZE: u64 = 64 * 1024;
+
+/// # Constant `MAX_MESSAGE_LENGTH`
+///
+/// The maximum length of a message; the diff shown in the confirmation message is truncated so
+/// that the message stays within this limit.
+const MAX_MESSAGE_LENGTH: usize = 2000;
+
+/// # Struct `Config`
+///
+/// The `config` command.
+pub struct Config;
+
+impl Command for Config {
+    fn name(&self) -> String {
+        String::from("config")
+    }
+
+    fn description(&self) -> String {
+        String::from("ConfigurationPlugin.ConfigCommand")
+    }
+
+    fn command_type(&self) -> CommandType {
+        CommandType::ChatInput
+    }
+
+    fn execute<'asynchronous_trait>(&self, ctx: CommandContext, _: InMemoryCache) -> FutureRetType<'asynchronous_trait, ()> {
+        Box::pin(execute_config_command(ctx))
+    }
+
+    fn required_cmdopts(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption::SubCommand(OptionsCommandOptionData {
+                description: String::from("uploads a new configuration from a `.toml` file attached to a message in this channel"),
+                name: String::from("upload"),
+                options: vec![
+                    CommandOption::String(ChoiceCommandOptionData {
+                        choices: vec![],
+                        description: String::from("the id of the message the `.toml` file is attached to"),
+                        name: String::from("message_id"),
+                        required: true
+                    })
+                ]
+            }),
+            CommandOption::SubCommand(OptionsCommandOptionData {
+                description: String::from("downloads the current configuration, or the default template if there is none"),
+                name: String::from("download"),
+                options: vec![]
+            })
+        ]
+    }
+}
+
+/// # Asynchronous Function `execute_config_command`
+///
+/// Executes the `config` command.
+///
+/// ## Parameters
+/// - `ctx`, type `CommandContext`: the command context to use.
+async fn execute_config_command(ctx: CommandContext) -> HarTexResult<()> {
+    let interaction = match ctx.interaction.clone() {
+        Interaction::ApplicationCommand(command) => command,
+        _ => return Err(
+            HarTexError::Custom {
+                message: String::from("invalid interaction type: expected ApplicationCommand")
+            }
+        )
+    };
+
+    let (guild_id, user_id) = match (interaction.guild_id, interaction.member.as_ref().and_then(|member| member.user.as_ref())) {
+        (Some(guild_id), Some(user)) => (guild_id, user.id),
+        _ => return respond(
+            &ctx,
+            interaction.id,
+            &interaction.token,
+            String::from(":x: This command can only be used in a guild."),
+            None
+        ).await
+    };
+
+    let current = ctx.repository.guild_config_source(guild_id).await?;
+
+    // a configuration that cannot be used or does not grant dashboard access to anyone does not
+    // restrict the access; the guild owner is then the only one with access
+    let current_config = current
+        .as_deref()
+        .and_then(|current| hartex_conftoml::from_string(current.to_string()).ok())
+        .filter(|config| !config.DashboardAccess.is_empty());
+
+    let access_level = match dashboard_access_level(&ctx, guild_id, user_id, current_config.as_ref()).await? {
+        Some(access_level) => access_level,
+        None => return respond(
+            &ctx,
+            interaction.id,
+            &interaction.token,
+            String::from(":x: You do not have dashboard access to the configuration of this guild."),
+            None
+        ).await
+    };
+
+    let (subcommand, options) = match interaction.data.options.first() {
+        Some(CommandDataOption::SubCommand { name, options }) => (name.as_str(), options.as_slice()),
+        _ => return Err(HarTexError::Custom {
+            message: String::from("expected a subcommand")
+        })
+    };
+
+    match subcommand {
+        "download" => {
+            // guilds without a configuration yet are given the default template to start from
+            let current = current.unwrap_or_else(template::default_config_template);
+
+            ctx.http
+                .interaction_callback(
+                    interaction.id,
+                    &interaction.token,
+                    &InteractionResponse::DeferredChannelMessageWithSource(
+                        CallbackData {
+                            allowed_mentions: None,
+                            components: None,
+                            content: None,
+                            embeds: vec![],
+                            flags: Some(MessageFlags::EPHEMERAL),
+                            tts: None
+                        }
+                    )
+                )
+                .exec()
+                .recorded("interaction_callback")
+                .await?;
+
+            let filename = format!("{guild_id}.toml");
+
+            ctx.http
+                .create_followup_message(&interaction.token)?
+                .ephemeral(true)
+                .files(&[(filename.as_str(), current.as_bytes())])
+                .exec()
+                .recorded("create_followup_message")
+                .await?;
+
+            Ok(())
+        }
+        "upload" => {
+            if access_level < AccessLevel::Editor {
+                return respond(
+                    &ctx,
+                    interaction.id,
+                    &interaction.token,
+                    String::from(":x: You need at least the `editor` access level to upload a configuration."),
+                    None
+                ).await;
+            }
+
+            let message_id = match string_from_options(options, "message_id")
+                .and_then(|id| id.parse::<u64>().ok())
+                .filter(|id| *id != 0) {
+                Some(id) => MessageId(id),
+                None => return respond(&ctx, interaction.id, &interaction.token, String::from(":x: Invalid message id."), None).await
+            };
+
+            let uploaded = match download_attachment(&ctx, interaction.channel_id, message_id).await {
+                Ok(uploaded) => uploaded,
+                Err(message) => return respond(&ctx, interaction.id, &interaction.token, message, None).await
+            };
+
+            // configurations written for older versions are upgraded before they are validated, and
+            // the upgraded configuration is the one that is saved
+            let (uploaded, migrations) = migration::migrate_source(&uploaded)?;
+            let report = validation::validate(&uploaded);
+            if report.has_errors() {
+                return respond(
+                    &ctx,
+                    interaction.id,
+                    &interaction.token,
+                    format!("The uploaded configuration is invalid:\n{report}", report = report.to_discord_message()),
+                    None
+                ).await;
+            }
+
+            let new_config = hartex_conftoml::from_string(uploaded.clone())?;
+            if let Some(current_config) = &current_config {
+                if !current_config.can_replace_with(user_id, &new_config) {
+                    return respond(
+                        &ctx,
+                        interaction.id,
+                        &interaction.token,
+                        format!(
+                            ":x: Your access level (`{level}`) does not allow you to make the changes to the dashboard access in the uploaded configuration.",
+                            level = access_level.name()
+                        ),
+                        None
+                    ).await;
+                }
+            }
+
+            let current = current.unwrap_or_default();
+
+            if current == uploaded {
+                return respond(
+                    &ctx,
+                    interaction.id,
+                    &interaction.token,
+                    String::from("The uploaded configuration is identical to the current configuration; nothing to do."),
+                    None
+                ).await;
+            }
+
+            let confirm_id = format!("config-upload-confirm-{interaction_id}", interaction_id = interaction.id);
+            let cancel_id = format!("config-upload-cancel-{interaction_id}", interaction_id = interaction.id);
+
+            // the warnings and applied migrations are shown above the diff; the rest of the message
+            // is left for the diff
+            let mut warnings = report.to_discord_message();
+            if !migrations.is_empty() {
+                warnings.push_str(&format!(
+                    "The uploaded configuration has been upgraded to version {version}:\n```\n{migrations}\n```\n",
+                    version = migration::CURRENT_VERSION
+                ));
+            }
+
+            let diff = render_diff(&current, &uploaded, MAX_MESSAGE_LENGTH.saturating_sub(warnings.len() + 150));
+
+            respond(
+                &ctx,
+                interaction.id,
+                &interaction.token,
+                format!("{warnings}The following changes will be made to the configuration of this guild:\n```diff\n{diff}\n```"),
+                Some(vec![Component::ActionRow(ActionRow {
+                    components: vec![
+                        Component::Button(Button {
+                            custom_id: Some(confirm_id.clone()),
+                            disabled: false,
+                            emoji: None,
+                            label: Some(String::from("Confirm")),
+                            style: ButtonStyle::Success,
+                            url: None
+                        }),
+                        Component::Button(Button {
+                            custom_id: Some(cancel_id.clone()),
+                            disabled: false,
+                            emoji: None,
+                            label: Some(String::from("Cancel")),
+                            style: ButtonStyle::Danger,
+                            url: None
+                        })
+                    ]
+                })])
+            ).await?;
+
+            let pending = ctx.standby.wait_for_event(move |event: &Event| match event {
+                Event::InteractionCreate(payload) => match &payload.0 {
+                    Interaction::MessageComponent(component) => {
+                        let clicked_by = component.member
+                            .as_ref()
+                            .and_then(|member| member.user.as_ref())
+                            .map(|user| user.id);
+
+                        clicked_by == Some(user_id)
+                            && (component.data.custom_id == confirm_id || component.data.custom_id == cancel_id)
+                    }
+                    _ => false
+                },
+                _ => false
+            });
+
+            let component = match tokio::time::timeout(CONFIRMATION_TIMEOUT, pending).await {
+                Ok(Ok(Event::InteractionCreate(payload))) => match payload.0 {
+                    Interaction::MessageComponent(component) => component,
+                    _ => unreachable!("the standby only matches message component interactions")
+                },
+                _ => {
+                    match ctx.http
+                        .update_interaction_original(&interaction.token)?
+                        .content(Some(":x: The upload has timed out; the configuration has not been changed."))
+                        .and_then(|update| update.components(Some(&[]))) {
+                        Ok(update) => update,
+                        Err(error) => {
+                            return Err(HarTexError::Custom {
+                                message: format!("failed to update original response: {error}")
+                            });
+                        }
+                    }
+                        .exec()
+                        .recorded("update_interaction_original")
+                        .await?;
+
+                    return Ok(());
+                }
+            };
+
+            let content = if component.data.custom_id.starts_with("config-upload-confirm") {
+                ctx.repository.update_guild_config(guild_id, uploaded).await?;
+                ctx.emitter.emit(HarTexEvent::ConfigurationUpdated(Box::new(ConfigurationUpdated {
+                    guild_id
+                })));
+
+                String::from(":white_check_mark: The configuration of this guild has been updated.")
+            }
+            else {
+                String::from("The upload has been cancelled; the configuration has not been changed.")
+            };
+
+            ctx.http
+                .interaction_callback(
+                    component.id,
+                    &component.token,
+                    &InteractionResponse::UpdateMessage(
+                        CallbackData {
+                            allowed_mentions: None,
+                            components: Some(vec![]),
+                            content: Some(content),
+                            embeds: vec![],
+                            flags: Some(MessageFlags::EPHEMERAL),
+                            tts: None
+                        }
+                    )
+                )
+                .exec()
+                .recorded("interaction_callback")
+                .await?;
+
+            Ok(())
+        }
+        _ => Err(HarTexError::Custom {
+            message: format!("unknown subcommand `{subcommand}`")
+        })
+    }
+}
+
+/// # Asynchronous Function `dashboard_access_level`
+///
+/// Returns the access level of a user to the configuration of a guild, as granted by the
+/// `DashboardAccess` entries of the current configuration. If the guild does not have a
+/// configuration restricting the access, the guild owner has the `owner` access level and nobody
+/// else has access.
+///
+/// ## Parameters
+/// - `ctx`, type `&CommandContext`: the command context to use.
+/// - `guild_id`, type `GuildId`: the guild to check the access for.
+/// - `user_id`, type `UserId`: the user to check the access of.
+/// - `current`, type `Option<&TomlConfig>`: the current configuration of the guild, if it restricts
+///                                          the access.
+async fn dashboard_access_level(
+    ctx: &CommandContext,
+    guild_id: GuildId,
+    user_id: UserId,
+    current: Option<&TomlConfig>
+) -> HarTexResult<Option<AccessLevel>> {
+    if let Some(config) = current {
+        return Ok(config.access_level(user_id));
+    }
+
+    let guild = ctx.http.guild(guild_id).exec().recorded("guild").await?.model().await?;
+
+    Ok((guild.owner_id == user_id).then(|| AccessLevel::Owner))
+}
+
+/// # Asynchronous Function `download_attachment`
+///
+/// Downloads the `.toml` file attached to a message; returns the message to respond with if the
+/// file cannot be downloaded.
+///
+/// ## Parameters
+/// - `ctx`, type `&CommandContext`: the command context to use.
+/// - `channel_id`, type `ChannelId`: the channel the message is in.
+/// - `message_id`, type `MessageId`: the message the file is attached to.
+async fn download_attachment(
+    ctx: &CommandContext,
+    channel_id: ChannelId,
+    message_id: MessageId
+) -> Result<String, String> {
+    let message = match ctx.http.message(channel_id, message_id).exec().recorded("message").await {
+        Ok(response) => match response.model().await {
+            Ok(message) => message,
+            Err(_) => return Err(String::from(":x: Could not read the message."))
+        },
+        Err(_) => return Err(String::from(":x: Could not find the message in this channel."))
+    };
+
+    let attachment = match message.attachments
+        .iter()
+        .find(|attachment| attachment.filename.ends_with(".toml")) {
+        Some(attachment) => attachment,
+        None => return Err(String::from(":x: The message does not have a `.toml` file attached."))
+    };
+
+    if attachment.size > MAX_CONFIG_SIZE {
+        return Err(format!(":x: The configuration file is too large; the maximum size is {MAX_CONFIG_SIZE} bytes."));
+    }
+
+    let bytes = match reqwest::get(&attachment.url).await {
+        Ok(response) => match response.bytes().await {
+            Ok(bytes) => bytes,
+            Err(_) => return Err(String::from(":x: Could not download the configuration file."))
+        },
+        Err(_) => return Err(String::from(":x: Could not download the configuration file."))
+    };
+
+    String::from_utf8(bytes.to_vec()).map_err(|_| String::from(":x: The configuration file is not valid UTF-8."))
+}
+
+/// # Function `render_diff`
+///
+/// Renders a unified diff between the current and the uploaded configuration, truncated to fit
+/// in a message.
+///
+/// ## Parameters
+/// - `current`, type `&str`: the current configuration.
+/// - `uploaded`, type `&str`: the uploaded configuration.
+/// - `max_length`, type `usize`: the maximum length of the rendered diff.
+fn render_diff(current: &str, uploaded: &str, max_length: usize) -> String {
+    let mut diff = TextDiff::from_lines(current, uploaded)
+        .unified_diff()
+        .context_radius(2)
+        .header("current", "uploaded")
+        .to_string();
+
+    if diff.len() > max_length {
+        let mut end = max_length;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        diff.truncate(end);
+        diff.push_str("\n... (diff truncated)");
+    }
+
+    diff
+}
+
+/// # Asynchronous Function `respond`
+///
+/// Responds to the interaction with an ephemeral message.
+///
+/// ## Parameters
+/// - `ctx`, type `&CommandContext`: the command context to use.
+/// - `interaction_id`, type `InteractionId`: the id of the interaction to respond to.
+/// - `token`, type `&str`: the token of the interaction to respond to.
+/// - `content`, type `String`: the content of the response.
+/// - `components`, type `Option<Vec<Component>>`: the components of the response, if any.
+async fn respond(
+    ctx: &CommandContext,
+    interaction_id: InteractionId,
+    token: &str,
+    content: String,
+    components: Option<Vec<Component>>
+) -> HarTexResult<()> {
+    ctx.http
+        .interaction_callback(
+            interaction_id,
+            token,
+            &InteractionResponse::ChannelMessageWithSource(
+                CallbackData {
+                    allowed_mentions: None,
+                    components,
+                    content: Some(content),
+                    embeds: vec![],
+                    flags: Some(MessageFlags::EPHEMERAL),
+                    tts: None
+                }
+            )
+        )
+        .exec()
+        .recorded("interaction_callback")
+        .await?;
+
+    Ok(())
+}
+
+/// # Function `string_from_options`
+///
+/// Returns the value of a string command option.
+///
+/// ## Parameters
+/// - `options`, type `&[CommandDataOption]`: the options of the subcommand.
+/// - `name`, type `&str`: the name of the option.
+fn string_from_options(options: &[CommandDataOption], name: &str) -> Option<String> {
+    options.iter().find_map(|option| match option {
+        CommandDataOption::String { name: option_name, value } if option_name == name => Some(value.clone()),
+        _ => None
+    })
+}
@@ -0,0 +1,6 @@
+//! # The `configuration` Module
+//!
+//! This module implements the Configuration plugin, which is for commands that manage the TOML
+//! configuration of a guild
+
+pub mod config;
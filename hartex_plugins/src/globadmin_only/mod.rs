@@ -0,0 +1,6 @@
+//! # The `globadmin_only` Module
+//!
+//! This module implements the Global Administrator Only plugin, which is for commands that may
+//! only be used by the global administrator of the bot
+
+pub mod whitelist;
@@ -0,0 +1,316 @@
+//! # The `whitelist` Module
+//!
+//! This module implements the `whitelist` command.
+
+use hartex_cmdsys::{
+    checks::{
+        isglobadmin::IsGlobAdmin,
+        Check,
+        CheckParams
+    },
+    command::{
+        Command,
+        CommandType
+    },
+    context::CommandContext
+};
+
+use hartex_core::{
+    discord::{
+        cache_inmemory::InMemoryCache,
+        model::{
+            application::{
+                callback::{
+                    CallbackData,
+                    InteractionResponse
+                },
+                command::{
+                    ChoiceCommandOptionData,
+                    CommandOption,
+                    OptionsCommandOptionData
+                },
+                interaction::{
+                    application_command::CommandDataOption,
+                    Interaction
+                }
+            },
+            channel::message::MessageFlags,
+            id::{
+                GuildId,
+                InteractionId
+            }
+        }
+    },
+    error::{
+        HarTexError,
+        HarTexResult
+    }
+};
+
+use hartex_logging::error;
+
+use hartex_metrics::http::RecordHttp;
+
+use hartex_utils::FutureRetType;
+
+/// # Struct `Whitelist`
+///
+/// The `whitelist` command.
+pub struct Whitelist;
+
+impl Command for Whitelist {
+    fn name(&self) -> String {
+        String::from("whitelist")
+    }
+
+    fn description(&self) -> String {
+        String::from("GlobAdminOnlyPlugin.WhitelistCommand")
+    }
+
+    fn command_type(&self) -> CommandType {
+        CommandType::ChatInput
+    }
+
+    fn execute<'asynchronous_trait>(&self, ctx: CommandContext, _: InMemoryCache) -> FutureRetType<'asynchronous_trait, ()> {
+        Box::pin(execute_whitelist_command(ctx))
+    }
+
+    fn required_cmdopts(&self) -> Vec<CommandOption> {
+        vec![
+            CommandOption::SubCommand(OptionsCommandOptionData {
+                description: String::from("lists the whitelisted guilds"),
+                name: String::from("list"),
+                options: vec![]
+            }),
+            CommandOption::SubCommand(OptionsCommandOptionData {
+                description: String::from("whitelists a guild, or reactivates its whitelist"),
+                name: String::from("add"),
+                options: vec![
+                    guild_id_option(),
+                    CommandOption::String(ChoiceCommandOptionData {
+                        choices: vec![],
+                        description: String::from("the name of the guild"),
+                        name: String::from("name"),
+                        required: true
+                    })
+                ]
+            }),
+            CommandOption::SubCommand(OptionsCommandOptionData {
+                description: String::from("deactivates the whitelist of a guild with a reason"),
+                name: String::from("deactivate"),
+                options: vec![
+                    guild_id_option(),
+                    CommandOption::String(ChoiceCommandOptionData {
+                        choices: vec![],
+                        description: String::from("the reason of the deactivation"),
+                        name: String::from("reason"),
+                        required: true
+                    })
+                ]
+            }),
+            CommandOption::SubCommand(OptionsCommandOptionData {
+                description: String::from("removes the whitelist of a guild"),
+                name: String::from("remove"),
+                options: vec![guild_id_option()]
+            })
+        ]
+    }
+}
+
+/// # Asynchronous Function `execute_whitelist_command`
+///
+/// Executes the `whitelist` command.
+///
+/// ## Parameters
+/// - `ctx`, type `CommandContext`: the command context to use.
+async fn execute_whitelist_command(ctx: CommandContext) -> HarTexResult<()> {
+    let interaction = match ctx.interaction.clone() {
+        Interaction::ApplicationCommand(command) => command,
+        _ => return Err(
+            HarTexError::Custom {
+                message: String::from("invalid interaction type: expected ApplicationCommand")
+            }
+        )
+    };
+
+    let user_id = interaction.member
+        .as_ref()
+        .and_then(|member| member.user.as_ref())
+        .or_else(|| interaction.user.as_ref())
+        .map(|user| user.id);
+    let params = match user_id {
+        Some(user_id) => CheckParams::builder().user_id(user_id).build(),
+        None => CheckParams::builder().build()
+    };
+
+    if IsGlobAdmin::execute(ctx.clone(), params).await.is_err() {
+        return respond(
+            &ctx,
+            interaction.id,
+            &interaction.token,
+            String::from(":x: This command can only be used by the global administrator.")
+        ).await;
+    }
+
+    // unwrapping here is fine as the user id has been checked to be the global administrator's
+    let user_id = user_id.unwrap();
+
+    let (subcommand, options) = match interaction.data.options.first() {
+        Some(CommandDataOption::SubCommand { name, options }) => (name.as_str(), options.as_slice()),
+        _ => return Err(HarTexError::Custom {
+            message: String::from("expected a subcommand")
+        })
+    };
+
+    let content = match subcommand {
+        "list" => {
+            match ctx.repository.whitelisted_guilds().await {
+                Ok(guilds) if guilds.is_empty() => String::from("There are no whitelisted guilds."),
+                Ok(guilds) => guilds
+                    .iter()
+                    .map(|guild| {
+                        let mut line = format!("`{id}` {name} - {status}", id = guild.GuildId, name = guild.GuildName, status = guild.Status);
+
+                        if let Some(reason) = &guild.Reason {
+                            line.push_str(&format!(" ({reason})"));
+                        }
+
+                        line
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                Err(error) => {
+                    error!("failed to retrieve the whitelisted guilds: {error:?}");
+
+                    String::from(":x: Failed to retrieve the whitelisted guilds; please try again later.")
+                }
+            }
+        }
+        "add" => {
+            let guild_id = match guild_id_from_options(options) {
+                Some(guild_id) => guild_id,
+                None => return respond(&ctx, interaction.id, &interaction.token, String::from(":x: Invalid guild id.")).await
+            };
+            let name = string_from_options(options, "name").unwrap_or_default();
+
+            match ctx.repository.add_whitelisted_guild(guild_id, name, user_id).await {
+                Ok(()) => format!(":white_check_mark: Guild `{guild_id}` is now whitelisted."),
+                Err(error) => {
+                    error!("failed to whitelist guild {guild_id}: {error:?}");
+
+                    String::from(":x: Failed to whitelist the guild; please try again later.")
+                }
+            }
+        }
+        "deactivate" => {
+            let guild_id = match guild_id_from_options(options) {
+                Some(guild_id) => guild_id,
+                None => return respond(&ctx, interaction.id, &interaction.token, String::from(":x: Invalid guild id.")).await
+            };
+            let reason = string_from_options(options, "reason").unwrap_or_default();
+
+            match ctx.repository.deactivate_whitelisted_guild(guild_id, reason).await {
+                Ok(true) => format!(":white_check_mark: The whitelist of guild `{guild_id}` is now deactivated."),
+                Ok(false) => format!(":x: Guild `{guild_id}` is not whitelisted."),
+                Err(error) => {
+                    error!("failed to deactivate the whitelist of guild {guild_id}: {error:?}");
+
+                    String::from(":x: Failed to deactivate the whitelist; please try again later.")
+                }
+            }
+        }
+        "remove" => {
+            let guild_id = match guild_id_from_options(options) {
+                Some(guild_id) => guild_id,
+                None => return respond(&ctx, interaction.id, &interaction.token, String::from(":x: Invalid guild id.")).await
+            };
+
+            match ctx.repository.remove_whitelisted_guild(guild_id).await {
+                Ok(true) => format!(":white_check_mark: The whitelist of guild `{guild_id}` has been removed."),
+                Ok(false) => format!(":x: Guild `{guild_id}` is not whitelisted."),
+                Err(error) => {
+                    error!("failed to remove the whitelist of guild {guild_id}: {error:?}");
+
+                    String::from(":x: Failed to remove the whitelist; please try again later.")
+                }
+            }
+        }
+        _ => return Err(HarTexError::Custom {
+            message: format!("unknown subcommand `{subcommand}`")
+        })
+    };
+
+    respond(&ctx, interaction.id, &interaction.token, content).await
+}
+
+/// # Asynchronous Function `respond`
+///
+/// Responds to the interaction with an ephemeral message.
+///
+/// ## Parameters
+/// - `ctx`, type `&CommandContext`: the command context to use.
+/// - `interaction_id`, type `InteractionId`: the id of the interaction to respond to.
+/// - `token`, type `&str`: the token of the interaction to respond to.
+/// - `content`, type `String`: the content of the response.
+async fn respond(ctx: &CommandContext, interaction_id: InteractionId, token: &str, content: String) -> HarTexResult<()> {
+    ctx.http
+        .interaction_callback(
+            interaction_id,
+            token,
+            &InteractionResponse::ChannelMessageWithSource(
+                CallbackData {
+                    allowed_mentions: None,
+                    components: None,
+                    content: Some(content),
+                    embeds: vec![],
+                    flags: Some(MessageFlags::EPHEMERAL),
+                    tts: None
+                }
+            )
+        )
+        .exec()
+        .recorded("interaction_callback")
+        .await?;
+
+    Ok(())
+}
+
+/// # Function `guild_id_option`
+///
+/// Returns the `guild_id` command option shared by the subcommands.
+fn guild_id_option() -> CommandOption {
+    CommandOption::String(ChoiceCommandOptionData {
+        choices: vec![],
+        description: String::from("the id of the guild"),
+        name: String::from("guild_id"),
+        required: true
+    })
+}
+
+/// # Function `guild_id_from_options`
+///
+/// Parses the `guild_id` command option; returns `None` if it is not a valid guild id.
+///
+/// ## Parameters
+/// - `options`, type `&[CommandDataOption]`: the options of the subcommand.
+fn guild_id_from_options(options: &[CommandDataOption]) -> Option<GuildId> {
+    string_from_options(options, "guild_id")?
+        .parse::<u64>()
+        .ok()
+        .filter(|id| *id != 0)
+        .map(GuildId)
+}
+
+/// # Function `string_from_options`
+///
+/// Returns the value of a string command option.
+///
+/// ## Parameters
+/// - `options`, type `&[CommandDataOption]`: the options of the subcommand.
+/// - `name`, type `&str`: the name of the option.
+fn string_from_options(options: &[CommandDataOption], name: &str) -> Option<String> {
+    options.iter().find_map(|option| match option {
+        CommandDataOption::String { name: option_name, value } if option_name == name => Some(value.clone()),
+        _ => None
+    })
+}
@@ -35,7 +35,7 @@ use hartex_core::{
     HARTEX_BUILD
 };
 
-use hartex_dbmani::whitelist::GetWhitelistedGuilds;
+use hartex_metrics::http::RecordHttp;
 
 use hartex_utils::FutureRetType;
 
@@ -69,7 +69,12 @@ impl Command for About {
 /// ## Parameters
 /// - `ctx`, type `CommandContext`: the command context to use.
 async fn execute_about_command(ctx: CommandContext) -> HarTexResult<()> {
-    let whitelists = GetWhitelistedGuilds::default().await?.len();
+    let whitelists = ctx.repository
+        .whitelisted_guilds()
+        .await?
+        .iter()
+        .filter(|guild| guild.is_active())
+        .count();
     let interaction = match ctx.interaction.clone() {
         Interaction::ApplicationCommand(command) => command,
         _ => return Err(
@@ -106,6 +111,7 @@ async fn execute_about_command(ctx: CommandContext) -> HarTexResult<()> {
             )
         )
         .exec()
+        .recorded("interaction_callback")
         .await?;
 
     Ok(())
@@ -28,6 +28,8 @@ use hartex_core::{
     }
 };
 
+use hartex_metrics::http::RecordHttp;
+
 use hartex_utils::{
     FutureRetType,
     shard_id
@@ -90,6 +92,7 @@ async fn execute_ping_command(ctx: CommandContext) -> HarTexResult<()> {
             )
         )
         .exec()
+        .recorded("interaction_callback")
         .await?;
 
     let shards = ctx.cluster.info();
@@ -112,6 +115,7 @@ async fn execute_ping_command(ctx: CommandContext) -> HarTexResult<()> {
         }
     }
         .exec()
+        .recorded("update_interaction_original")
         .await?;
 
     Ok(())
@@ -28,6 +28,8 @@ use hartex_core::{
     }
 };
 
+use hartex_metrics::http::RecordHttp;
+
 use hartex_utils::FutureRetType;
 
 /// # Struct `Source`
@@ -89,6 +91,7 @@ async fn execute_source_command(ctx: CommandContext) -> HarTexResult<()> {
             )
         )
         .exec()
+        .recorded("interaction_callback")
         .await?;
 
     Ok(())
@@ -32,6 +32,8 @@ use hartex_core::{
     }
 };
 
+use hartex_metrics::http::RecordHttp;
+
 use hartex_utils::FutureRetType;
 
 /// # Struct `Team`
@@ -95,6 +97,7 @@ async fn execute_team_command(ctx: CommandContext) -> HarTexResult<()> {
             )
         )
         .exec()
+        .recorded("interaction_callback")
         .await?;
 
     Ok(())
@@ -12,13 +12,20 @@ use hartex_cmdsys::{
 
 use hartex_core::{
     discord::{
-        cache_inmemory::InMemoryCache,
+        cache_inmemory::{
+            InMemoryCache,
+            ResourceType
+        },
         embed_builder::{
             EmbedAuthorBuilder,
             EmbedBuilder,
             EmbedFieldBuilder,
             ImageSource
         },
+        gateway::{
+            EventTypeFlags,
+            Intents
+        },
         model::{
             application::{
                 callback::{
@@ -47,6 +54,8 @@ use hartex_core::{
     }
 };
 
+use hartex_metrics::http::RecordHttp;
+
 use hartex_utils::{
     cdn::{
         Cdn,
@@ -86,6 +95,19 @@ impl Command for Userinfo {
             })
         ]
     }
+
+    // the presence of the user is obtained from the cache
+    fn required_intents(&self) -> Intents {
+        Intents::GUILD_PRESENCES
+    }
+
+    fn required_event_types(&self) -> EventTypeFlags {
+        EventTypeFlags::PRESENCE_UPDATE
+    }
+
+    fn required_resource_types(&self) -> ResourceType {
+        ResourceType::PRESENCE
+    }
 }
 
 /// # Asynchronous Function `execute_userinfo_command`
@@ -121,6 +143,7 @@ async fn execute_userinfo_command(ctx: CommandContext, cache: InMemoryCache) ->
                 )
             )
             .exec()
+            .recorded("interaction_callback")
             .await?;
     }
 
@@ -148,6 +171,7 @@ async fn execute_userinfo_command(ctx: CommandContext, cache: InMemoryCache) ->
         ctx.http
             .user(UserId::from(value.parse::<u64>().unwrap()))
             .exec()
+            .recorded("user")
             .await?
             .model()
             .await?
@@ -157,6 +181,7 @@ async fn execute_userinfo_command(ctx: CommandContext, cache: InMemoryCache) ->
         // a guild (which its id should never be None)
         .guild_member(interaction.guild_id.unwrap(), user.id)
         .exec()
+        .recorded("guild_member")
         .await?
         .model()
         .await?;
@@ -244,6 +269,7 @@ async fn execute_userinfo_command(ctx: CommandContext, cache: InMemoryCache) ->
             )
         )
         .exec()
+        .recorded("interaction_callback")
         .await?;
 
     Ok(())
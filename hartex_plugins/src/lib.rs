@@ -4,5 +4,7 @@
 
 #![feature(format_args_capture)]
 
+pub mod configuration;
+pub mod globadmin_only;
 pub mod global;
 pub mod information;
\ No newline at end of file